@@ -91,14 +91,16 @@ impl<T> CallerAllocatedArray<T> {
         (ptr, len)
     }
 
-    /// Returns the length of the array
+    /// Returns the length of the array, 0 for a null pointer
     pub fn len(&self) -> usize {
-        self.len
+        if self.ptr.is_null() { 0 } else { self.len }
     }
 
-    /// Returns true if the array is empty
+    /// Returns true if the array has no accessible elements
+    ///
+    /// See [null and empty arrays](super#null-and-empty-arrays).
     pub fn is_empty(&self) -> bool {
-        self.len == 0
+        self.len() == 0
     }
 
     /// Checks if the pointer is null
@@ -177,6 +179,15 @@ impl<T> Default for CallerAllocatedArray<T> {
     }
 }
 
+impl<T> PartialEq for CallerAllocatedArray<T> {
+    /// Compares pointer identity and length, not the elements.
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr && self.len == other.len
+    }
+}
+
+impl<T> Eq for CallerAllocatedArray<T> {}
+
 impl<T> Clone for CallerAllocatedArray<T> {
     /// Creates a shallow copy of the pointer.
     ///
@@ -257,14 +268,16 @@ impl<T> CalleeAllocatedArray<T> {
         (ptr, len)
     }
 
-    /// Returns the length of the array
+    /// Returns the length of the array, 0 for a null pointer
     pub fn len(&self) -> usize {
-        self.len
+        if self.ptr.is_null() { 0 } else { self.len }
     }
 
-    /// Returns true if the array is empty
+    /// Returns true if the array has no accessible elements
+    ///
+    /// See [null and empty arrays](super#null-and-empty-arrays).
     pub fn is_empty(&self) -> bool {
-        self.len == 0
+        self.len() == 0
     }
 
     /// Checks if the pointer is null
@@ -272,6 +285,18 @@ impl<T> CalleeAllocatedArray<T> {
         self.ptr.is_null()
     }
 
+    /// Returns the start and one-past-the-end pointers if it's not null
+    ///
+    /// Useful to forward the array to native APIs taking a `(begin, end)` pair.
+    pub fn as_ptr_range(&self) -> Option<std::ops::Range<*const T>> {
+        if self.ptr.is_null() {
+            None
+        } else {
+            let start = self.ptr as *const T;
+            Some(start..start.wrapping_add(self.len))
+        }
+    }
+
     /// Returns a slice of the array if it's not null
     ///
     /// # Safety
@@ -323,6 +348,45 @@ impl<T> CalleeAllocatedArray<T> {
             unsafe { Some(&mut *self.ptr.add(index)) }
         }
     }
+
+    /// Transforms each element into an owned value, stopping at the first error
+    ///
+    /// A null array yields an empty `Vec`. The array itself is still freed on drop.
+    ///
+    /// The elements are read under the contract of [`new`](Self::new): the
+    /// pointer is valid and points to `len` initialized elements.
+    pub fn try_map<U, E, F>(&self, f: F) -> Result<Vec<U>, E>
+    where
+        F: Fn(&T) -> Result<U, E>,
+    {
+        // The array was created from a valid callee allocation of `len` elements.
+        match unsafe { self.as_slice() } {
+            Some(slice) => slice.iter().map(f).collect(),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+impl CalleeAllocatedArray<u16> {
+    /// Splits a double-null-terminated block of wide strings (`MULTI_SZ`)
+    ///
+    /// Decoding stops at the first empty string or at the end of the array, so a missing
+    /// final null is tolerated. Invalid UTF-16 is replaced with `U+FFFD`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the pointer is valid and points to `len` initialized elements.
+    pub unsafe fn to_string_list(&self) -> Vec<String> {
+        let Some(slice) = (unsafe { self.as_slice() }) else {
+            return Vec::new();
+        };
+
+        slice
+            .split(|&c| c == 0)
+            .take_while(|segment| !segment.is_empty())
+            .map(String::from_utf16_lossy)
+            .collect()
+    }
 }
 
 impl<T> Drop for CalleeAllocatedArray<T> {
@@ -345,3 +409,12 @@ impl<T> Default for CalleeAllocatedArray<T> {
         }
     }
 }
+
+impl<T> PartialEq for CalleeAllocatedArray<T> {
+    /// Compares pointer identity and length, not the elements.
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr && self.len == other.len
+    }
+}
+
+impl<T> Eq for CalleeAllocatedArray<T> {}
@@ -6,6 +6,14 @@
 //! COM memory management follows two patterns:
 //! 1. Caller allocates, callee frees (e.g., input parameters)
 //! 2. Callee allocates, caller frees (e.g., output parameters)
+//!
+//! # Null and empty arrays
+//!
+//! The array wrappers tell a missing array from an allocated empty one. A
+//! null pointer has a length of 0 whatever length it was created with, has no
+//! slice and nothing to free. A non-null pointer with a length of 0 is empty,
+//! yields an empty slice and is still freed on drop by the wrappers that own
+//! it. Use `is_null` to tell the two apart.
 
 pub mod array;
 pub mod ptr;
@@ -15,7 +23,7 @@ pub mod wstring;
 // Re-export all public types for convenience
 pub use array::{CalleeAllocatedArray, CallerAllocatedArray};
 pub use ptr::{CalleeAllocatedPtr, CallerAllocatedPtr};
-pub use ptr_array::{CalleeAllocatedPtrArray, CallerAllocatedPtrArray};
+pub use ptr_array::{CalleeAllocatedPtrArray, CalleeAllocatedStructArray, CallerAllocatedPtrArray};
 pub use wstring::{CalleeAllocatedWString, CallerAllocatedWString};
 
 #[cfg(test)]
@@ -116,6 +116,15 @@ impl<T> Default for CallerAllocatedPtr<T> {
     }
 }
 
+impl<T> PartialEq for CallerAllocatedPtr<T> {
+    /// Compares pointer identity, not the pointed-to values.
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl<T> Eq for CallerAllocatedPtr<T> {}
+
 impl<T> Clone for CallerAllocatedPtr<T> {
     /// Creates a shallow copy of the pointer.
     ///
@@ -238,3 +247,12 @@ impl<T> Default for CalleeAllocatedPtr<T> {
         }
     }
 }
+
+impl<T> PartialEq for CalleeAllocatedPtr<T> {
+    /// Compares pointer identity, not the pointed-to values.
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl<T> Eq for CalleeAllocatedPtr<T> {}
@@ -90,14 +90,16 @@ impl<T> CallerAllocatedPtrArray<T> {
         (ptr, len)
     }
 
-    /// Returns the length of the array
+    /// Returns the length of the array, 0 for a null pointer
     pub fn len(&self) -> usize {
-        self.len
+        if self.ptr.is_null() { 0 } else { self.len }
     }
 
-    /// Returns true if the array is empty
+    /// Returns true if the array has no accessible elements
+    ///
+    /// See [null and empty arrays](super#null-and-empty-arrays).
     pub fn is_empty(&self) -> bool {
-        self.len == 0
+        self.len() == 0
     }
 
     /// Checks if the pointer is null
@@ -159,6 +161,22 @@ impl<T> CallerAllocatedPtrArray<T> {
             true
         }
     }
+
+    /// Replaces the pointer at the given index, returning the previous one
+    ///
+    /// Returns `None` without writing if the index is out of bounds or the array is null.
+    /// The caller becomes responsible for the lifetime of the returned pointer.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the array pointer is valid and the new pointer is valid.
+    pub unsafe fn replace(&mut self, index: usize, value: *mut T) -> Option<*mut T> {
+        if index >= self.len || self.ptr.is_null() {
+            None
+        } else {
+            unsafe { Some(ptr::replace(self.ptr.add(index), value)) }
+        }
+    }
 }
 
 impl<T> Drop for CallerAllocatedPtrArray<T> {
@@ -179,6 +197,15 @@ impl<T> Default for CallerAllocatedPtrArray<T> {
     }
 }
 
+impl<T> PartialEq for CallerAllocatedPtrArray<T> {
+    /// Compares pointer identity and length, not the elements.
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr && self.len == other.len
+    }
+}
+
+impl<T> Eq for CallerAllocatedPtrArray<T> {}
+
 impl<T> Clone for CallerAllocatedPtrArray<T> {
     /// Creates a shallow copy of the pointer.
     ///
@@ -265,14 +292,16 @@ impl<T> CalleeAllocatedPtrArray<T> {
         (ptr, len)
     }
 
-    /// Returns the length of the array
+    /// Returns the length of the array, 0 for a null pointer
     pub fn len(&self) -> usize {
-        self.len
+        if self.ptr.is_null() { 0 } else { self.len }
     }
 
-    /// Returns true if the array is empty
+    /// Returns true if the array has no accessible elements
+    ///
+    /// See [null and empty arrays](super#null-and-empty-arrays).
     pub fn is_empty(&self) -> bool {
-        self.len == 0
+        self.len() == 0
     }
 
     /// Checks if the pointer is null
@@ -334,6 +363,22 @@ impl<T> CalleeAllocatedPtrArray<T> {
             true
         }
     }
+
+    /// Replaces the pointer at the given index, returning the previous one
+    ///
+    /// Returns `None` without writing if the index is out of bounds or the array is null.
+    /// The caller becomes responsible for the lifetime of the returned pointer.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the array pointer is valid and the new pointer is valid.
+    pub unsafe fn replace(&mut self, index: usize, value: *mut T) -> Option<*mut T> {
+        if index >= self.len || self.ptr.is_null() {
+            None
+        } else {
+            unsafe { Some(ptr::replace(self.ptr.add(index), value)) }
+        }
+    }
 }
 
 impl<T> Drop for CalleeAllocatedPtrArray<T> {
@@ -364,3 +409,90 @@ impl<T> Default for CalleeAllocatedPtrArray<T> {
         }
     }
 }
+
+impl<T> PartialEq for CalleeAllocatedPtrArray<T> {
+    /// Compares pointer identity and length, not the elements.
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr && self.len == other.len
+    }
+}
+
+impl<T> Eq for CalleeAllocatedPtrArray<T> {}
+
+/// A smart pointer for callee-allocated pointer arrays whose elements own further COM memory
+///
+/// Works like [`CalleeAllocatedPtrArray`], but runs `cleanup` on each element before the
+/// element itself is freed, so nested allocations (e.g. `PWSTR` fields or blobs inside an
+/// OPC result struct) are released too.
+///
+/// # Memory Management
+/// - Runs `cleanup` on each non-null element
+/// - Frees each individual pointer in the array
+/// - Frees the array container itself
+pub struct CalleeAllocatedStructArray<T, F: FnMut(&mut T)> {
+    array: CalleeAllocatedPtrArray<T>,
+    cleanup: F,
+}
+
+impl<T, F: FnMut(&mut T)> CalleeAllocatedStructArray<T, F> {
+    /// Creates a new `CalleeAllocatedStructArray` from a raw pointer, length and cleanup
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` is a valid pointer to an array of `len` pointers
+    /// allocated by the callee, each null or pointing to an initialized `T`, and that
+    /// all of them will be freed using `CoTaskMemFree`.
+    pub unsafe fn new(ptr: *mut *mut T, len: usize, cleanup: F) -> Self {
+        Self {
+            array: CalleeAllocatedPtrArray::from_raw(ptr, len),
+            cleanup,
+        }
+    }
+
+    /// Returns the underlying pointer array
+    pub fn as_ptr_array(&self) -> &CalleeAllocatedPtrArray<T> {
+        &self.array
+    }
+
+    /// Returns the raw pointer and transfers ownership to the caller
+    ///
+    /// After calling this method, neither the cleanup nor any free will run.
+    pub fn into_raw(self) -> (*mut *mut T, usize) {
+        let mut this = std::mem::ManuallyDrop::new(self);
+        let array = std::mem::take(&mut this.array);
+        unsafe { ptr::drop_in_place(&mut this.cleanup) };
+        array.into_raw()
+    }
+
+    /// Returns the length of the array
+    pub fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    /// Returns true if the array is empty
+    pub fn is_empty(&self) -> bool {
+        self.array.is_empty()
+    }
+}
+
+impl<T, F: FnMut(&mut T)> Drop for CalleeAllocatedStructArray<T, F> {
+    fn drop(&mut self) {
+        if let Some(elements) = unsafe { self.array.as_slice() } {
+            for &element in elements {
+                if !element.is_null() {
+                    (self.cleanup)(unsafe { &mut *element });
+                }
+            }
+        }
+        // `self.array` then frees each element and the container
+    }
+}
+
+impl<T, F: FnMut(&mut T)> std::fmt::Debug for CalleeAllocatedStructArray<T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CalleeAllocatedStructArray")
+            .field("ptr", &self.array.as_ptr())
+            .field("len", &self.array.len())
+            .finish_non_exhaustive()
+    }
+}
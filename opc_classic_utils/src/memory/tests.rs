@@ -63,6 +63,33 @@ fn test_transparent_repr() {
     assert_eq!(callee_wstring.as_ptr(), wstring_ptr);
 }
 
+#[test]
+fn test_pointer_identity_eq() {
+    let mut value = 42i32;
+    let mut other = 42i32;
+
+    // Equal pointers compare equal, equal values behind different pointers do not
+    assert_eq!(
+        CallerAllocatedPtr::from_raw(&mut value as *mut i32),
+        CallerAllocatedPtr::from_raw(&mut value as *mut i32)
+    );
+    assert_ne!(
+        CallerAllocatedPtr::from_raw(&mut value as *mut i32),
+        CallerAllocatedPtr::from_raw(&mut other as *mut i32)
+    );
+
+    // Arrays also compare their length
+    let ptr = &mut value as *mut i32;
+    assert_eq!(
+        CallerAllocatedArray::from_raw(ptr, 1),
+        CallerAllocatedArray::from_raw(ptr, 1)
+    );
+    assert_ne!(
+        CallerAllocatedArray::from_raw(ptr, 1),
+        CallerAllocatedArray::from_raw(ptr, 0)
+    );
+}
+
 #[test]
 fn test_caller_allocated_ptr_allocate() {
     // Test allocation of caller-allocated pointer
@@ -113,6 +140,28 @@ fn test_caller_allocated_wstring_from_string() {
     }
 }
 
+#[test]
+fn test_wstring_to_string_strict() {
+    let wstring = CallerAllocatedWString::from_str("Random.Int4").unwrap();
+    unsafe {
+        assert_eq!(wstring.to_string_strict().unwrap(), "Random.Int4");
+    }
+
+    // An unpaired high surrogate is replaced by the lossy path and rejected by the strict one
+    let mut invalid = [0x0041u16, 0xD800, 0x0042, 0];
+    let callee =
+        std::mem::ManuallyDrop::new(CalleeAllocatedWString::from_raw(invalid.as_mut_ptr()));
+    unsafe {
+        assert_eq!(callee.to_string().unwrap(), "A\u{FFFD}B");
+        assert!(callee.to_string_strict().is_err());
+    }
+
+    let null = CallerAllocatedWString::default();
+    unsafe {
+        assert!(null.to_string_strict().is_err());
+    }
+}
+
 #[test]
 fn test_caller_allocated_wstring_from_os_str() {
     // Test creating wide string from OsStr
@@ -274,6 +323,61 @@ fn test_callee_allocated_array_frees_container() {
     // When _array goes out of scope, it should call CoTaskMemFree on the container
 }
 
+#[test]
+fn test_callee_allocated_array_as_ptr_range() {
+    let (ptr, len) = CallerAllocatedArray::from_slice(&[1u32, 2, 3])
+        .unwrap()
+        .into_raw();
+    let array = CalleeAllocatedArray::from_raw(ptr, len);
+
+    let range = array.as_ptr_range().unwrap();
+    assert_eq!(range.start, ptr as *const u32);
+    assert_eq!(unsafe { range.end.offset_from(range.start) }, 3);
+    assert_eq!(unsafe { array.as_slice() }.unwrap().as_ptr_range(), range);
+
+    assert!(
+        CalleeAllocatedArray::<u32>::default()
+            .as_ptr_range()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_callee_allocated_array_try_map() {
+    let (ptr, len) = CallerAllocatedArray::from_slice(&[1, 2, 3])
+        .unwrap()
+        .into_raw();
+    let array = CalleeAllocatedArray::from_raw(ptr, len);
+
+    let doubled: Result<Vec<i64>, ()> = array.try_map(|value| Ok(i64::from(*value) * 2));
+    assert_eq!(doubled, Ok(vec![2, 4, 6]));
+
+    let failed: Result<Vec<i32>, i32> =
+        array.try_map(|value| if *value == 2 { Err(*value) } else { Ok(*value) });
+    assert_eq!(failed, Err(2));
+
+    let null = CalleeAllocatedArray::<i32>::default();
+    let empty: Result<Vec<i32>, ()> = null.try_map(|value| Ok(*value));
+    assert_eq!(empty, Ok(vec![]));
+}
+
+#[test]
+fn test_callee_allocated_array_to_string_list() {
+    let to_list = |wide: &[u16]| {
+        let (ptr, len) = CallerAllocatedArray::from_slice(wide).unwrap().into_raw();
+        unsafe { CalleeAllocatedArray::from_raw(ptr, len).to_string_list() }
+    };
+    let wide = |s: &str| s.encode_utf16().collect::<Vec<_>>();
+
+    assert_eq!(to_list(&wide("Low\0High\0\0")), vec!["Low", "High"]);
+    // Missing final null
+    assert_eq!(to_list(&wide("Low\0High")), vec!["Low", "High"]);
+    // Anything after the double null is ignored
+    assert_eq!(to_list(&wide("Low\0\0High\0\0")), vec!["Low"]);
+    assert!(to_list(&wide("\0\0")).is_empty());
+    assert!(unsafe { CalleeAllocatedArray::<u16>::default().to_string_list() }.is_empty());
+}
+
 #[test]
 fn test_caller_allocated_ptr_array_null() {
     let array = CallerAllocatedPtrArray::<i32>::default();
@@ -316,6 +420,29 @@ fn test_caller_allocated_ptr_array_from_slice() {
     }
 }
 
+#[test]
+fn test_callee_allocated_struct_array_cleanup() {
+    let ptrs = vec![
+        CallerAllocatedPtr::from_value(&1i32).unwrap().into_raw(),
+        std::ptr::null_mut::<i32>(),
+        CallerAllocatedPtr::from_value(&2i32).unwrap().into_raw(),
+    ];
+    let (ptr, len) = CallerAllocatedPtrArray::from_ptr_slice(&ptrs)
+        .unwrap()
+        .into_raw();
+
+    let mut cleaned = Vec::new();
+    {
+        let array = unsafe {
+            CalleeAllocatedStructArray::new(ptr, len, |value: &mut i32| cleaned.push(*value))
+        };
+        assert_eq!(array.len(), 3);
+    }
+
+    // Cleanup runs for every non-null element before it is freed
+    assert_eq!(cleaned, vec![1, 2]);
+}
+
 #[test]
 fn test_caller_allocated_ptr_array_access() {
     let mut array = CallerAllocatedPtrArray::<i32>::allocate(2).unwrap();
@@ -337,6 +464,22 @@ fn test_caller_allocated_ptr_array_access() {
     }
 }
 
+#[test]
+fn test_caller_allocated_ptr_array_replace() {
+    let mut first = 1i32;
+    let mut second = 2i32;
+    let mut array = CallerAllocatedPtrArray::from_ptr_slice(&[&mut first as *mut i32]).unwrap();
+
+    unsafe {
+        let previous = array.replace(0, &mut second).unwrap();
+        assert_eq!(*previous, 1);
+        assert_eq!(*array.get(0).unwrap(), 2);
+
+        // Out of bounds leaves the array untouched
+        assert!(array.replace(1, std::ptr::null_mut()).is_none());
+    }
+}
+
 #[test]
 fn test_callee_allocated_ptr_array_frees_all() {
     // This test verifies that CalleeAllocatedPtrArray frees both container and elements
@@ -348,27 +491,80 @@ fn test_callee_allocated_ptr_array_frees_all() {
 
 #[test]
 fn test_array_transparent_repr() {
-    // Test that transparent repr works correctly for arrays
+    // Test that transparent repr works correctly for arrays, `len` reports 0
+    // for the null pointers so the stored length is read back with `into_raw`
     let ptr = std::ptr::null_mut::<i32>();
     let len = 5;
 
     // CallerAllocatedArray should have the same memory layout as (*mut i32, usize)
     let caller_array = CallerAllocatedArray::from_raw(ptr, len);
     assert_eq!(caller_array.as_ptr(), ptr);
-    assert_eq!(caller_array.len(), len);
+    assert_eq!(caller_array.into_raw().1, len);
 
     // CalleeAllocatedArray should have the same memory layout as (*mut i32, usize)
     let callee_array = CalleeAllocatedArray::from_raw(ptr, len);
     assert_eq!(callee_array.as_ptr(), ptr);
-    assert_eq!(callee_array.len(), len);
+    assert_eq!(callee_array.into_raw().1, len);
 
     // Pointer arrays should have the same memory layout as (*mut *mut i32, usize)
     let ptr_array = std::ptr::null_mut::<*mut i32>();
     let caller_ptr_array = CallerAllocatedPtrArray::from_raw(ptr_array, len);
     assert_eq!(caller_ptr_array.as_ptr(), ptr_array);
-    assert_eq!(caller_ptr_array.len(), len);
+    assert_eq!(caller_ptr_array.into_raw().1, len);
 
     let callee_ptr_array = CalleeAllocatedPtrArray::from_raw(ptr_array, len);
     assert_eq!(callee_ptr_array.as_ptr(), ptr_array);
-    assert_eq!(callee_ptr_array.len(), len);
+    assert_eq!(callee_ptr_array.into_raw().1, len);
+}
+
+#[test]
+fn test_arrays_null_versus_empty() {
+    // Allocating nothing yields a null array.
+    let caller = CallerAllocatedArray::<u32>::allocate(0).unwrap();
+    assert!(caller.is_null());
+    assert!(caller.is_empty());
+    assert!(unsafe { caller.as_slice() }.is_none());
+
+    let caller_ptrs = CallerAllocatedPtrArray::<u16>::allocate(0).unwrap();
+    assert!(caller_ptrs.is_null());
+    assert!(caller_ptrs.is_empty());
+
+    // A null array has no elements whatever its length.
+    let callee = CalleeAllocatedArray::<u32>::from_raw(std::ptr::null_mut(), 3);
+    assert!(callee.is_empty());
+    assert_eq!(callee.len(), 0);
+    assert!(unsafe { callee.as_slice() }.is_none());
+
+    let callee_ptrs = CalleeAllocatedPtrArray::<u16>::from_raw(std::ptr::null_mut(), 3);
+    assert!(callee_ptrs.is_empty());
+    assert_eq!(callee_ptrs.len(), 0);
+    assert!(unsafe { callee_ptrs.as_slice() }.is_none());
+
+    // An allocated array of length 0 is empty but not null, and is still freed.
+    let (ptr, _) = CallerAllocatedArray::<u32>::allocate(1).unwrap().into_raw();
+    let callee = CalleeAllocatedArray::from_raw(ptr, 0);
+    assert!(callee.is_empty());
+    assert!(!callee.is_null());
+    assert_eq!(unsafe { callee.as_slice() }, Some(&[][..]));
+    drop(callee);
+
+    let (ptr, _) = CallerAllocatedPtrArray::<u16>::allocate(1)
+        .unwrap()
+        .into_raw();
+    let callee_ptrs = CalleeAllocatedPtrArray::from_raw(ptr, 0);
+    assert!(callee_ptrs.is_empty());
+    assert!(!callee_ptrs.is_null());
+    assert_eq!(unsafe { callee_ptrs.as_slice() }.map(<[_]>::len), Some(0));
+}
+
+#[test]
+fn test_callee_allocated_wstring_into_string() {
+    let ptr = CallerAllocatedWString::from_str("Random.Int4")
+        .unwrap()
+        .into_raw();
+    let callee = CalleeAllocatedWString::from_raw(ptr);
+    unsafe {
+        assert_eq!(callee.into_string().unwrap(), "Random.Int4");
+        assert!(CalleeAllocatedWString::default().into_string().is_none());
+    }
 }
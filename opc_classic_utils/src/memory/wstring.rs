@@ -88,6 +88,18 @@ impl CallerAllocatedWString {
         Some(os_string.to_string_lossy().into_owned())
     }
 
+    /// Converts the wide string to a Rust string, failing on invalid UTF-16
+    ///
+    /// Unlike [`Self::to_string`], unpaired surrogates are reported as an error instead of
+    /// being replaced, which matters for identifiers that are passed back to the server.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the pointer is valid and points to a null-terminated wide string.
+    pub unsafe fn to_string_strict(&self) -> Result<String, windows::core::Error> {
+        unsafe { to_string_strict(self.ptr) }
+    }
+
     /// Converts the wide string to an `OsString`
     ///
     /// # Safety
@@ -145,6 +157,15 @@ impl Default for CallerAllocatedWString {
     }
 }
 
+impl PartialEq for CallerAllocatedWString {
+    /// Compares pointer identity, not the pointed-to values.
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl Eq for CallerAllocatedWString {}
+
 impl Clone for CallerAllocatedWString {
     /// Creates a shallow copy of the pointer.
     ///
@@ -228,6 +249,30 @@ impl CalleeAllocatedWString {
         Some(os_string.to_string_lossy().into_owned())
     }
 
+    /// Converts the wide string to a Rust string and frees it
+    ///
+    /// Like [`Self::to_string`], but consumes the wrapper so the COM memory is released
+    /// before this returns instead of whenever the wrapper is dropped.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the pointer is valid and points to a null-terminated wide string.
+    pub unsafe fn into_string(self) -> Option<String> {
+        unsafe { self.to_string() }
+    }
+
+    /// Converts the wide string to a Rust string, failing on invalid UTF-16
+    ///
+    /// Unlike [`Self::to_string`], unpaired surrogates are reported as an error instead of
+    /// being replaced, which matters for identifiers that are passed back to the server.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the pointer is valid and points to a null-terminated wide string.
+    pub unsafe fn to_string_strict(&self) -> Result<String, windows::core::Error> {
+        unsafe { to_string_strict(self.ptr) }
+    }
+
     /// Converts the wide string to an `OsString`
     ///
     /// # Safety
@@ -289,6 +334,15 @@ impl Default for CalleeAllocatedWString {
     }
 }
 
+impl PartialEq for CalleeAllocatedWString {
+    /// Compares pointer identity, not the pointed-to values.
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl Eq for CalleeAllocatedWString {}
+
 impl Clone for CalleeAllocatedWString {
     /// Creates a shallow copy of the pointer.
     ///
@@ -300,3 +354,25 @@ impl Clone for CalleeAllocatedWString {
         Self { ptr: self.ptr }
     }
 }
+
+unsafe fn to_string_strict(ptr: *const u16) -> Result<String, windows::core::Error> {
+    if ptr.is_null() {
+        return Err(windows::core::Error::new(
+            windows::core::HRESULT(0x80004003u32 as i32), // E_POINTER
+            "Null wide string pointer",
+        ));
+    }
+
+    let mut len = 0;
+    while unsafe { *ptr.add(len) } != 0 {
+        len += 1;
+    }
+
+    let slice = unsafe { std::slice::from_raw_parts(ptr, len) };
+    String::from_utf16(slice).map_err(|_| {
+        windows::core::Error::new(
+            windows::core::HRESULT::from_win32(1113), // ERROR_NO_UNICODE_TRANSLATION
+            "Invalid UTF-16 in wide string",
+        )
+    })
+}
@@ -18,5 +18,5 @@ pub mod v3;
 pub use iterator::*;
 pub use traits::*;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "async"))]
 mod tests;
@@ -132,3 +132,120 @@ fn test_client() {
     let cloned_value = state.vDataValue.clone();
     SyncIoTrait::write(&group, &[server_handle], &[cloned_value]).expect("Failed to write");
 }
+
+#[windows::core::implement(opc_comn_bindings::IOPCServerList)]
+struct MockServerList {
+    categories: std::sync::Mutex<Vec<(Vec<windows::core::GUID>, Vec<windows::core::GUID>)>>,
+}
+
+impl opc_comn_bindings::IOPCServerList_Impl for MockServerList_Impl {
+    fn EnumClassesOfCategories(
+        &self,
+        cimplemented: u32,
+        rgcatidimpl: *const windows::core::GUID,
+        crequired: u32,
+        rgcatidreq: *const windows::core::GUID,
+    ) -> windows::core::Result<windows::Win32::System::Com::IEnumGUID> {
+        let read = |count: u32, pointer: *const windows::core::GUID| {
+            if count == 0 {
+                Vec::new()
+            } else {
+                unsafe { std::slice::from_raw_parts(pointer, count as usize) }.to_vec()
+            }
+        };
+
+        self.categories
+            .lock()
+            .unwrap()
+            .push((read(cimplemented, rgcatidimpl), read(crequired, rgcatidreq)));
+
+        Err(windows::Win32::Foundation::E_NOTIMPL.into())
+    }
+
+    fn GetClassDetails(
+        &self,
+        _clsid: *const windows::core::GUID,
+        _ppszprogid: *mut windows::core::PWSTR,
+        _ppszusertype: *mut windows::core::PWSTR,
+    ) -> windows::core::Result<()> {
+        Err(windows::Win32::Foundation::E_NOTIMPL.into())
+    }
+
+    fn CLSIDFromProgID(
+        &self,
+        _szprogid: &windows::core::PCWSTR,
+    ) -> windows::core::Result<windows::core::GUID> {
+        Err(windows::Win32::Foundation::E_NOTIMPL.into())
+    }
+}
+
+#[test]
+fn test_server_filter_categories() {
+    use crate::def::{ServerFilter, Version};
+
+    let mock = windows::core::ComObject::new(MockServerList {
+        categories: Default::default(),
+    });
+    let servers: opc_comn_bindings::IOPCServerList = mock.to_interface();
+
+    let filter = ServerFilter::default()
+        .implements(Version::V2)
+        .implements(Version::V3)
+        .requires(Version::V2);
+    let (implemented, required) = filter.category_ids();
+    assert!(enum_classes(&servers, &implemented, &required).is_err());
+
+    let categories = mock.categories.lock().unwrap();
+    assert_eq!(
+        *categories,
+        vec![(
+            vec![Version::V2.to_guid(), Version::V3.to_guid()],
+            vec![Version::V2.to_guid()],
+        )]
+    );
+}
+
+#[test]
+fn test_supports_interface_releases_reference() {
+    use windows::core::Interface as _;
+
+    let mock = windows::core::ComObject::new(MockServerList {
+        categories: Default::default(),
+    });
+    let unknown: windows::core::IUnknown = mock.to_interface();
+
+    assert!(crate::utils::supports_interface(
+        &unknown,
+        &opc_comn_bindings::IOPCServerList::IID
+    ));
+    assert!(!crate::utils::supports_interface(
+        &unknown,
+        &opc_da_bindings::IOPCServer::IID
+    ));
+
+    // `unknown` and `mock` are the only references left.
+    let references = unsafe { (unknown.vtable().AddRef)(unknown.as_raw()) };
+    unsafe { (unknown.vtable().Release)(unknown.as_raw()) };
+    assert_eq!(references, 3);
+}
+
+#[test]
+fn test_resolve_clsid() {
+    use crate::utils::{parse_guid, resolve_clsid};
+
+    let guid = windows::core::GUID::from_u128(0xf8582cf2_88fb_11d0_b850_00c0f0104305);
+    assert_eq!(
+        parse_guid("F8582CF2-88FB-11D0-B850-00C0F0104305").unwrap(),
+        guid
+    );
+    assert_eq!(
+        resolve_clsid(" {f8582cf2-88fb-11d0-b850-00c0f0104305} ").unwrap(),
+        guid
+    );
+
+    let error = resolve_clsid("{F8582CF2-88FB}").unwrap_err();
+    assert_eq!(error.code(), windows::Win32::Foundation::E_INVALIDARG);
+
+    let error = resolve_clsid("Not.A.Registered.ProgID").unwrap_err();
+    assert_ne!(error.code(), windows::Win32::Foundation::E_INVALIDARG);
+}
@@ -2,7 +2,7 @@ use windows_core::Interface as _;
 
 use crate::{
     client::GuidIterator,
-    def::{ClassContext, ServerInfo},
+    def::{ClassContext, ServerFilter, ServerInfo},
     utils::{IntoBridge, ToNative, TryToNative as _},
 };
 
@@ -17,31 +17,22 @@ pub trait ClientTrait<Server: TryFrom<windows::core::IUnknown, Error = windows::
     ///
     /// A `Result` containing a `GuidIterator` over server GUIDs, or an error if the operation fails.
     fn get_servers(&self) -> windows::core::Result<GuidIterator> {
-        let id = unsafe {
-            windows::Win32::System::Com::CLSIDFromProgID(windows::core::w!("OPC.ServerList.1"))?
-        };
-
-        let servers: opc_comn_bindings::IOPCServerList = unsafe {
-            // TODO: Use CoCreateInstanceEx
-            windows::Win32::System::Com::CoCreateInstance(
-                &id,
-                None,
-                // TODO: Convert from filters
-                windows::Win32::System::Com::CLSCTX_ALL,
-            )?
-        };
-
         let versions = [Self::CATALOG_ID];
 
-        let iter = unsafe {
-            servers
-                .EnumClassesOfCategories(&versions, &versions)
-                .map_err(|e| {
-                    windows::core::Error::new(e.code(), "Failed to enumerate server classes")
-                })?
-        };
+        enum_servers(&versions, &versions)
+    }
 
-        Ok(GuidIterator::new(iter))
+    /// Retrieves an iterator over the GUIDs of servers matching `filter`.
+    ///
+    /// Unlike [`get_servers`](Self::get_servers), the listed versions do not
+    /// depend on the client version.
+    fn get_servers_with_filter(
+        &self,
+        filter: &ServerFilter,
+    ) -> windows::core::Result<GuidIterator> {
+        let (implemented, required) = filter.category_ids();
+
+        enum_servers(&implemented, &required)
     }
 
     /// Creates a server instance from the specified class ID.
@@ -106,3 +97,42 @@ pub trait ClientTrait<Server: TryFrom<windows::core::IUnknown, Error = windows::
         }
     }
 }
+
+/// Enumerates server classes through `OPC.ServerList`.
+fn enum_servers(
+    implemented: &[windows::core::GUID],
+    required: &[windows::core::GUID],
+) -> windows::core::Result<GuidIterator> {
+    let id = unsafe {
+        windows::Win32::System::Com::CLSIDFromProgID(windows::core::w!("OPC.ServerList.1"))?
+    };
+
+    let servers: opc_comn_bindings::IOPCServerList = unsafe {
+        // TODO: Use CoCreateInstanceEx
+        windows::Win32::System::Com::CoCreateInstance(
+            &id,
+            None,
+            // TODO: Convert from filters
+            windows::Win32::System::Com::CLSCTX_ALL,
+        )?
+    };
+
+    enum_classes(&servers, implemented, required)
+}
+
+/// Enumerates the server classes of `servers` matching the categories.
+pub(crate) fn enum_classes(
+    servers: &opc_comn_bindings::IOPCServerList,
+    implemented: &[windows::core::GUID],
+    required: &[windows::core::GUID],
+) -> windows::core::Result<GuidIterator> {
+    let iter = unsafe {
+        servers
+            .EnumClassesOfCategories(implemented, required)
+            .map_err(|e| {
+                windows::core::Error::new(e.code(), "Failed to enumerate server classes")
+            })?
+    };
+
+    Ok(GuidIterator::new(iter))
+}
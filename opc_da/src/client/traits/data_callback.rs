@@ -3,6 +3,9 @@ use crate::{
     utils::RemoteArray,
 };
 
+/// Upper bound for item counts reported by the server in callbacks.
+const MAX_CALLBACK_COUNT: u32 = 0x0010_0000;
+
 #[windows::core::implement(
     // implicit implement IUnknown
     opc_da_bindings::IOPCDataCallback,
@@ -50,11 +53,11 @@ impl<'a, T: DataCallbackTrait + 'a> opc_da_bindings::IOPCDataCallback_Impl
         timestamps: *const windows::Win32::Foundation::FILETIME,
         errors: *const windows_core::HRESULT,
     ) -> windows_core::Result<()> {
-        let client_items = RemoteArray::from_ptr(client_items, count);
-        let values = RemoteArray::from_ptr(values, count);
-        let qualities = RemoteArray::from_ptr(qualities, count);
-        let timestamps = RemoteArray::from_ptr(timestamps, count);
-        let errors = RemoteArray::from_ptr(errors, count);
+        let client_items = RemoteArray::from_raw_checked(client_items, count, MAX_CALLBACK_COUNT);
+        let values = RemoteArray::from_raw_checked(values, count, MAX_CALLBACK_COUNT);
+        let qualities = RemoteArray::from_raw_checked(qualities, count, MAX_CALLBACK_COUNT);
+        let timestamps = RemoteArray::from_raw_checked(timestamps, count, MAX_CALLBACK_COUNT);
+        let errors = RemoteArray::from_raw_checked(errors, count, MAX_CALLBACK_COUNT);
 
         self.on_data_change(DataChangeEvent {
             transaction_id,
@@ -82,11 +85,11 @@ impl<'a, T: DataCallbackTrait + 'a> opc_da_bindings::IOPCDataCallback_Impl
         timestamps: *const windows::Win32::Foundation::FILETIME,
         errors: *const windows_core::HRESULT,
     ) -> windows_core::Result<()> {
-        let client_items = RemoteArray::from_ptr(client_items, count);
-        let values = RemoteArray::from_ptr(values, count);
-        let qualities = RemoteArray::from_ptr(qualities, count);
-        let timestamps = RemoteArray::from_ptr(timestamps, count);
-        let errors = RemoteArray::from_ptr(errors, count);
+        let client_items = RemoteArray::from_raw_checked(client_items, count, MAX_CALLBACK_COUNT);
+        let values = RemoteArray::from_raw_checked(values, count, MAX_CALLBACK_COUNT);
+        let qualities = RemoteArray::from_raw_checked(qualities, count, MAX_CALLBACK_COUNT);
+        let timestamps = RemoteArray::from_raw_checked(timestamps, count, MAX_CALLBACK_COUNT);
+        let errors = RemoteArray::from_raw_checked(errors, count, MAX_CALLBACK_COUNT);
 
         self.on_read_complete(ReadCompleteEvent {
             transaction_id,
@@ -110,8 +113,9 @@ impl<'a, T: DataCallbackTrait + 'a> opc_da_bindings::IOPCDataCallback_Impl
         client_handles: *const u32,
         errors: *const windows_core::HRESULT,
     ) -> windows_core::Result<()> {
-        let client_handles = RemoteArray::from_ptr(client_handles, count);
-        let errors = RemoteArray::from_ptr(errors, count);
+        let client_handles =
+            RemoteArray::from_raw_checked(client_handles, count, MAX_CALLBACK_COUNT);
+        let errors = RemoteArray::from_raw_checked(errors, count, MAX_CALLBACK_COUNT);
 
         self.on_write_complete(WriteCompleteEvent {
             transaction_id,
@@ -70,6 +70,27 @@ pub trait ServerTrait<Group: TryFrom<windows::core::IUnknown, Error = windows::c
         }
     }
 
+    /// Gets a private group by its name.
+    ///
+    /// # Arguments
+    /// * `name` - Name of a group created by this client
+    ///
+    /// # Returns
+    /// The group object
+    ///
+    /// # Errors
+    /// Returns E_INVALIDARG if no group with that name exists
+    fn get_group_by_name(&self, name: &str) -> windows::core::Result<Group> {
+        let group_name = LocalPointer::from(name);
+
+        let group = unsafe {
+            self.interface()?
+                .GetGroupByName(group_name.as_pcwstr(), &opc_da_bindings::IOPCItemMgt::IID)?
+        };
+
+        group.try_into()
+    }
+
     /// Gets the current server status.
     ///
     /// # Returns
@@ -82,6 +103,21 @@ pub trait ServerTrait<Group: TryFrom<windows::core::IUnknown, Error = windows::c
         Ok(RemotePointer::from_raw(status))
     }
 
+    /// Gets an error description string.
+    ///
+    /// # Arguments
+    /// * `error` - HRESULT error code to get description for
+    /// * `locale_id` - Locale of the description
+    fn get_error_string(
+        &self,
+        error: windows::core::HRESULT,
+        locale_id: u32,
+    ) -> windows::core::Result<String> {
+        let output = unsafe { self.interface()?.GetErrorString(error, locale_id)? };
+
+        RemotePointer::from(output).try_into()
+    }
+
     /// Removes a group from the server.
     ///
     /// # Arguments
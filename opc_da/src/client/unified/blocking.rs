@@ -0,0 +1,116 @@
+use tokio_stream::StreamExt as _;
+
+use super::{Group, ItemUpdates};
+use crate::{
+    def::{DataSourceTarget, ItemValue, Variant},
+    error,
+};
+
+/// A blocking facade over [`Group`] for synchronous applications.
+///
+/// Futures are driven on a single-threaded tokio runtime owned by the
+/// facade, so callers never deal with `async`. All other group methods stay
+/// available through `Deref`.
+///
+/// # Example
+/// ```ignore
+/// let group = BlockingGroup::new(server.add_group(GroupBuilder::new("Group"))?)?;
+/// let value = group.read("Tag1")?;
+/// ```
+pub struct BlockingGroup {
+    group: Box<Group>,
+    runtime: tokio::runtime::Runtime,
+    updates: std::cell::RefCell<Option<ItemUpdates>>,
+}
+
+impl BlockingGroup {
+    /// Wraps an initialized group.
+    ///
+    /// # Errors
+    /// Returns `E_FAIL` if the runtime cannot be created.
+    pub fn new(group: Box<Group>) -> windows::core::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .map_err(|e| error::fail(format!("Failed to create runtime: {e}")))?;
+
+        Ok(Self {
+            group,
+            runtime,
+            updates: std::cell::RefCell::new(None),
+        })
+    }
+
+    /// Reads a single item from the device.
+    pub fn read(&self, name: &str) -> windows::core::Result<ItemValue> {
+        self.group.read(name)
+    }
+
+    /// Writes a single value without quality or timestamp.
+    pub fn write(&self, name: &str, value: Variant) -> windows::core::Result<()> {
+        self.group.write(name, value)
+    }
+
+    /// Refreshes all active items and waits up to `timeout` for the
+    /// resulting callback.
+    ///
+    /// # Errors
+    /// Returns `RPC_E_TIMEOUT` if the callback does not arrive in time, the
+    /// refresh then stays pending on the server.
+    ///
+    /// # Panics
+    /// Panics if called from within an async runtime, where blocking on the
+    /// facade's own runtime is not allowed.
+    pub fn refresh(
+        &self,
+        source: DataSourceTarget,
+        timeout: std::time::Duration,
+    ) -> windows::core::Result<()> {
+        let future = self.group.refresh(source)?;
+        match self.runtime.block_on(tokio::time::timeout(timeout, future)) {
+            Ok(result) => result.map(|_| ()),
+            Err(_) => Err(windows::core::Error::new(
+                windows::Win32::Foundation::RPC_E_TIMEOUT,
+                "refresh timed out",
+            )),
+        }
+    }
+
+    /// Waits up to `timeout` for the next subscription update.
+    ///
+    /// The subscription starts on the first call and is kept, so updates
+    /// arriving between calls are queued rather than lost. Items added after
+    /// the first call are not resolved, see [`Group::subscribe`].
+    ///
+    /// # Returns
+    /// The changed item with its name, or `None` on timeout.
+    pub fn read_blocking_subscription(
+        &self,
+        timeout: std::time::Duration,
+    ) -> windows::core::Result<Option<(String, ItemValue)>> {
+        let mut updates = self.updates.borrow_mut();
+        let updates = updates.get_or_insert_with(|| self.group.subscribe());
+
+        match self
+            .runtime
+            .block_on(tokio::time::timeout(timeout, updates.next()))
+        {
+            Ok(Some(update)) => Ok(Some(update)),
+            Ok(None) => Err(error::fail("Data change channel closed")),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Returns the wrapped group.
+    pub fn into_inner(self) -> Box<Group> {
+        self.group
+    }
+}
+
+impl std::ops::Deref for BlockingGroup {
+    type Target = Group;
+
+    fn deref(&self) -> &Self::Target {
+        &self.group
+    }
+}
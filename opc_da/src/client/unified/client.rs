@@ -1,6 +1,6 @@
 use crate::{
     client::{v1, v2, v3, ClientTrait as _, GuidIterator},
-    def::ClassContext,
+    def::{ClassContext, ServerFilter},
 };
 
 use super::Server;
@@ -33,6 +33,17 @@ impl Client {
         }
     }
 
+    pub fn get_servers_with_filter(
+        &self,
+        filter: &ServerFilter,
+    ) -> windows::core::Result<GuidIterator> {
+        match self {
+            Client::V1(client) => client.get_servers_with_filter(filter),
+            Client::V2(client) => client.get_servers_with_filter(filter),
+            Client::V3(client) => client.get_servers_with_filter(filter),
+        }
+    }
+
     pub fn create_server(&self, class_id: windows::core::GUID) -> windows::core::Result<Server> {
         match self {
             Client::V1(client) => Ok(Server::V1(
@@ -46,6 +57,93 @@ impl Client {
             )),
         }
     }
+
+    /// Bounds outgoing COM calls of the calling apartment to `timeout`.
+    ///
+    /// Installs a message filter (`CoRegisterMessageFilter`) that cancels a
+    /// call still pending after `timeout`, failing it with
+    /// `RPC_E_CALL_CANCELED`, and stops retrying calls the server rejected as
+    /// busy, failing them with `RPC_E_CALL_REJECTED`. This replaces any filter
+    /// registered before. COM consults the filter when a window message
+    /// arrives during the call, so the cancellation happens at the first
+    /// message after the timeout.
+    ///
+    /// The filter affects every COM call made from the apartment, not just
+    /// OPC calls, and lasts until [`clear_call_timeout`](Self::clear_call_timeout).
+    ///
+    /// Message filters are only supported in single-threaded apartments. A
+    /// client wrapped by [`Guard::new`](super::Guard::new) lives in the
+    /// multithreaded apartment, where this always fails; create it with
+    /// [`Guard::new_single_threaded`](super::Guard::new_single_threaded)
+    /// instead.
+    ///
+    /// The filter only covers calls made on the calling thread. The worker
+    /// threads of [`Group::read_items_sync_timeout`](super::Group::read_items_sync_timeout)
+    /// and [`Group::poll_changes`](super::Group::poll_changes) call through
+    /// their own proxies in the multithreaded apartment, unaffected by it.
+    ///
+    /// # Errors
+    /// Returns `CO_E_NOT_SUPPORTED` in a multithreaded apartment.
+    pub fn set_call_timeout(&self, timeout: std::time::Duration) -> windows::core::Result<()> {
+        let filter: windows::Win32::Media::Audio::IMessageFilter = CallTimeoutFilter {
+            timeout_ms: u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX),
+        }
+        .into();
+
+        unsafe { windows::Win32::Media::Audio::CoRegisterMessageFilter(&filter, None) }
+    }
+
+    /// Removes the message filter installed by [`set_call_timeout`](Self::set_call_timeout).
+    pub fn clear_call_timeout(&self) -> windows::core::Result<()> {
+        unsafe { windows::Win32::Media::Audio::CoRegisterMessageFilter(None, None) }
+    }
+}
+
+/// Message filter cancelling outgoing calls that take longer than `timeout_ms`.
+#[windows::core::implement(windows::Win32::Media::Audio::IMessageFilter)]
+struct CallTimeoutFilter {
+    timeout_ms: u32,
+}
+
+impl windows::Win32::Media::Audio::IMessageFilter_Impl for CallTimeoutFilter_Impl {
+    fn HandleInComingCall(
+        &self,
+        _dwcalltype: u32,
+        _htaskcaller: windows::Win32::Media::HTASK,
+        _dwtickcount: u32,
+        _lpinterfaceinfo: *const windows::Win32::System::Com::INTERFACEINFO,
+    ) -> u32 {
+        windows::Win32::System::Com::SERVERCALL_ISHANDLED.0 as u32
+    }
+
+    fn RetryRejectedCall(
+        &self,
+        _htaskcallee: windows::Win32::Media::HTASK,
+        dwtickcount: u32,
+        dwrejecttype: u32,
+    ) -> u32 {
+        // Retrying after 100 ms until the timeout, -1 cancels the call.
+        if dwrejecttype == windows::Win32::System::Com::SERVERCALL_RETRYLATER.0 as u32
+            && dwtickcount < self.timeout_ms
+        {
+            100
+        } else {
+            u32::MAX
+        }
+    }
+
+    fn MessagePending(
+        &self,
+        _htaskcallee: windows::Win32::Media::HTASK,
+        dwtickcount: u32,
+        _dwpendingtype: u32,
+    ) -> u32 {
+        if dwtickcount >= self.timeout_ms {
+            windows::Win32::System::Com::PENDINGMSG_CANCELCALL.0 as u32
+        } else {
+            windows::Win32::System::Com::PENDINGMSG_WAITDEFPROCESS.0 as u32
+        }
+    }
 }
 
 impl From<v1::Client> for Client {
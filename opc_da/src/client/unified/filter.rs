@@ -0,0 +1,63 @@
+use crate::def::{ItemValue, Variant};
+
+/// Decides whether an item update is worth broadcasting.
+///
+/// Installed with [`Group::set_change_filter`](super::Group::set_change_filter)
+/// and consulted for every item of a data change, against the last value
+/// broadcast for the same item. The first update of an item and items that
+/// report an error are always broadcast.
+pub trait ChangeFilter: Send + Sync {
+    /// Returns `true` if `next` differs enough from `prev` to be broadcast.
+    fn is_significant(&self, prev: &ItemValue, next: &ItemValue) -> bool;
+}
+
+/// Broadcasts every change of value or quality, ignoring timestamp-only updates.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnyChange;
+
+impl ChangeFilter for AnyChange {
+    fn is_significant(&self, prev: &ItemValue, next: &ItemValue) -> bool {
+        prev.quality != next.quality || Variant::from(&prev.value) != Variant::from(&next.value)
+    }
+}
+
+/// Broadcasts numeric changes larger than an absolute deadband.
+///
+/// Quality changes are always significant. Values that are not numeric, or
+/// that change type, fall back to [`AnyChange`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericDeadband(pub f64);
+
+impl ChangeFilter for NumericDeadband {
+    fn is_significant(&self, prev: &ItemValue, next: &ItemValue) -> bool {
+        if prev.quality != next.quality {
+            return true;
+        }
+
+        let prev = Variant::from(&prev.value);
+        let next = Variant::from(&next.value);
+        match (as_f64(&prev), as_f64(&next)) {
+            (Some(prev), Some(next)) if prev.is_nan() || next.is_nan() => {
+                prev.is_nan() != next.is_nan()
+            }
+            (Some(prev), Some(next)) => (next - prev).abs() > self.0,
+            _ => prev != next,
+        }
+    }
+}
+
+fn as_f64(value: &Variant) -> Option<f64> {
+    Some(match *value {
+        Variant::I8(value) => value.into(),
+        Variant::I16(value) => value.into(),
+        Variant::I32(value) => value.into(),
+        Variant::I64(value) => value as f64,
+        Variant::U8(value) => value.into(),
+        Variant::U16(value) => value.into(),
+        Variant::U32(value) => value.into(),
+        Variant::U64(value) => value as f64,
+        Variant::F32(value) => value.into(),
+        Variant::F64(value) => value,
+        _ => return None,
+    })
+}
@@ -2,16 +2,21 @@ use std::collections::{BTreeMap, HashMap};
 
 use windows_core::{ComObjectInner as _, IUnknown, Interface};
 
+use super::{ChangeFilter, GlobalInterface, Guard, ThreadAffinity};
 use crate::{
     client::{
         v1, v2, v3, AsyncIo2Trait, AsyncIo3Trait, ConnectionPointContainerTrait, DataCallback,
-        DataCallbackTrait, ItemMgtTrait, SyncIo2Trait, SyncIoTrait,
+        DataCallbackTrait, GroupStateMgtTrait, ItemMgtTrait, PublicGroupStateMgtTrait,
+        SyncIo2Trait, SyncIoTrait,
     },
     def::{
-        CancelCompleteEvent, DataChangeEvent, DataSourceTarget, ItemDef, ItemPartialValue,
-        ItemResult, ItemState, ItemValue, ReadCompleteEvent, WriteCompleteEvent,
+        extend_item_values, item_vqt_to_native, CancelCompleteEvent, DataChangeEvent,
+        DataSourceTarget, GroupState, GroupStateUpdate, ItemDef, ItemPartialValue, ItemResult,
+        ItemValue, OwnedDataChangeEvent, ReadCompleteEvent, ReadResults, SubscriptionSpec, Variant,
+        WriteCompleteEvent,
     },
-    utils::{IntoBridge as _, TryToLocal as _, TryToNative as _},
+    error,
+    utils::{IntoBridge as _, StringWriteCache, TryToLocal as _, TryToNative as _},
 };
 
 pub struct Group {
@@ -21,6 +26,7 @@ pub struct Group {
     initialized: bool,
     data_callback_cookie: Option<u32>,
     data_change_broadcaster: tokio::sync::broadcast::Sender<DataChangeEvent>,
+    owned_data_change_broadcaster: tokio::sync::broadcast::Sender<OwnedDataChangeEvent>,
     data_change_awaiters:
         std::sync::Mutex<BTreeMap<u32, tokio::sync::oneshot::Sender<DataChangeEvent>>>,
     read_complete_awaiters:
@@ -29,6 +35,16 @@ pub struct Group {
         std::sync::Mutex<BTreeMap<u32, tokio::sync::oneshot::Sender<WriteCompleteEvent>>>,
     cancel_complete_awaiters:
         std::sync::Mutex<BTreeMap<u32, tokio::sync::oneshot::Sender<CancelCompleteEvent>>>,
+    value_cache: Option<ValueCache>,
+    add_batch_size: usize,
+    coerce_write_types: bool,
+    allow_null_writes: bool,
+    max_async_version: u8,
+    broadcast_capacity: usize,
+    change_filter: Option<Box<dyn ChangeFilter>>,
+    broadcast_values: std::sync::Mutex<HashMap<u32, ItemValue>>,
+    canonical_types: std::sync::Mutex<HashMap<String, u16>>,
+    affinity: ThreadAffinity,
 }
 
 pub enum GroupInner {
@@ -43,9 +59,97 @@ pub struct Item {
     pub client_handle: u32,
 }
 
+/// Client-side cache of values returned by `read_sync`, keyed by item name.
+struct ValueCache {
+    ttl: std::time::Duration,
+    entries: std::sync::Mutex<HashMap<String, CachedValue>>,
+}
+
+struct CachedValue {
+    value: windows::Win32::System::Variant::VARIANT,
+    quality: u16,
+    timestamp: std::time::SystemTime,
+    cached_at: std::time::Instant,
+}
+
+impl ValueCache {
+    fn new(ttl: std::time::Duration) -> Self {
+        Self {
+            ttl,
+            entries: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<String, CachedValue>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn get(&self, name: &str, max_age: std::time::Duration) -> Option<ItemValue> {
+        let entries = self.entries();
+        let entry = entries.get(name)?;
+        let age = entry.cached_at.elapsed();
+
+        if age > max_age || age > self.ttl {
+            return None;
+        }
+
+        Some(ItemValue {
+            value: entry.value.clone(),
+            quality: entry.quality,
+            timestamp: entry.timestamp,
+        })
+    }
+
+    fn insert(&self, name: &str, value: &ItemValue) {
+        self.entries().insert(
+            name.to_string(),
+            CachedValue {
+                value: value.value.clone(),
+                quality: value.quality,
+                timestamp: value.timestamp,
+                cached_at: std::time::Instant::now(),
+            },
+        );
+    }
+
+    fn invalidate(&self, name: &str) {
+        self.entries().remove(name);
+    }
+}
+
 impl Group {
-    fn new(inner: GroupInner) -> Self {
-        let data_change_broadcaster = tokio::sync::broadcast::Sender::new(32);
+    /// Capacity of the data change channels used by [`Group::new`].
+    pub const DEFAULT_BROADCAST_CAPACITY: usize = 32;
+
+    /// Number of items sent per `AddItems` call by [`Group::add`] by default.
+    pub const DEFAULT_ADD_BATCH_SIZE: usize = 500;
+
+    /// Creates a unified group.
+    ///
+    /// # Arguments
+    /// * `inner` - The version specific group
+    /// * `value_cache_ttl` - Enables the client-side value cache when `Some`,
+    ///   entries older than the TTL are never served
+    ///
+    /// The group is bound to the calling thread. Debug builds panic when its
+    /// methods are called from another thread.
+    pub fn new(inner: GroupInner, value_cache_ttl: Option<std::time::Duration>) -> Self {
+        Self::with_broadcast_capacity(inner, value_cache_ttl, Self::DEFAULT_BROADCAST_CAPACITY)
+    }
+
+    /// Creates a unified group whose data change channels hold `capacity` events.
+    ///
+    /// A subscriber that falls more than `capacity` events behind loses the
+    /// oldest ones, see [`ItemUpdates::lagged`]. A capacity of 0 is raised to 1.
+    pub fn with_broadcast_capacity(
+        inner: GroupInner,
+        value_cache_ttl: Option<std::time::Duration>,
+        capacity: usize,
+    ) -> Self {
+        let capacity = capacity.max(1);
+        let data_change_broadcaster = tokio::sync::broadcast::Sender::new(capacity);
 
         Self {
             inner,
@@ -54,14 +158,26 @@ impl Group {
             initialized: false,
             data_callback_cookie: None,
             data_change_broadcaster,
+            owned_data_change_broadcaster: tokio::sync::broadcast::Sender::new(capacity),
             data_change_awaiters: std::sync::Mutex::new(BTreeMap::new()),
             read_complete_awaiters: std::sync::Mutex::new(BTreeMap::new()),
             write_complete_awaiters: std::sync::Mutex::new(BTreeMap::new()),
             cancel_complete_awaiters: std::sync::Mutex::new(BTreeMap::new()),
+            value_cache: value_cache_ttl.map(ValueCache::new),
+            add_batch_size: Self::DEFAULT_ADD_BATCH_SIZE,
+            coerce_write_types: false,
+            allow_null_writes: false,
+            max_async_version: 3,
+            broadcast_capacity: capacity,
+            change_filter: None,
+            broadcast_values: std::sync::Mutex::new(HashMap::new()),
+            canonical_types: std::sync::Mutex::new(HashMap::new()),
+            affinity: ThreadAffinity::current(),
         }
     }
 
     pub fn initialize(&mut self) -> windows::core::Result<()> {
+        self.affinity.check("Group");
         if self.initialized {
             return Ok(());
         }
@@ -89,27 +205,168 @@ impl Group {
         Ok(())
     }
 
+    /// Initializes the group and checks that the server can call back.
+    ///
+    /// `Advise` succeeds even when DCOM permissions or a firewall block the
+    /// callbacks, so this also requests a refresh from the cache and waits
+    /// for its `OnDataChange`. The group needs at least one active item, as
+    /// servers reject refreshing an empty group.
+    ///
+    /// # Errors
+    /// Returns `RPC_E_TIMEOUT` if no callback arrives within `timeout` and
+    /// `E_NOTIMPL` for V1 groups, which cannot refresh.
+    pub async fn initialize_verified(
+        &mut self,
+        timeout: std::time::Duration,
+    ) -> windows::core::Result<()> {
+        if let GroupInner::V1(_) = self.inner {
+            return Err(error::not_impl("initialize_verified requires OPC DA 2.0"));
+        }
+
+        self.initialize()?;
+
+        let refresh = self.refresh(DataSourceTarget::ForceCache)?;
+        let transaction_id = refresh.transaction_id();
+
+        match tokio::time::timeout(timeout, refresh).await {
+            Ok(result) => result.map(|_| ()),
+            Err(_) => {
+                self.data_change_awaiters
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .remove(&transaction_id);
+
+                Err(windows::core::Error::new(
+                    windows::Win32::Foundation::RPC_E_TIMEOUT,
+                    "Advise succeeded but no refresh callback arrived, check the DCOM \
+                     callback permissions and the firewall of the client machine",
+                ))
+            }
+        }
+    }
+
     pub fn data_change_receiver(&self) -> tokio::sync::broadcast::Receiver<DataChangeEvent> {
         self.data_change_broadcaster.subscribe()
     }
 
+    /// Streams decoded data changes, reporting lag instead of failing.
+    ///
+    /// When the subscriber falls behind, the dropped events are reported as
+    /// [`SubscriptionEvent::Lagged`] and the stream continues, so callers can
+    /// resync, e.g. with [`refresh`](Self::refresh).
+    pub fn data_change_stream(
+        &self,
+    ) -> impl tokio_stream::Stream<Item = SubscriptionEvent> + Send + 'static {
+        use tokio_stream::StreamExt as _;
+
+        tokio_stream::wrappers::BroadcastStream::new(self.owned_data_change_broadcaster.subscribe())
+            .map(|event| match event {
+                Ok(event) => SubscriptionEvent::Change(event),
+                Err(tokio_stream::wrappers::errors::BroadcastStreamRecvError::Lagged(skipped)) => {
+                    SubscriptionEvent::Lagged(skipped)
+                }
+            })
+    }
+
+    /// Subscribes to value changes, yielding each changed item with its name.
+    ///
+    /// Items are resolved by the names known when subscribing, items that
+    /// report an error are skipped. If the subscriber falls behind, the
+    /// dropped events are logged as a warning and counted in
+    /// [`ItemUpdates::lagged`].
+    pub fn subscribe(&self) -> ItemUpdates {
+        ItemUpdates {
+            names: self
+                .items
+                .values()
+                .map(|item| (item.client_handle, item.name.clone()))
+                .collect(),
+            events: tokio_stream::wrappers::BroadcastStream::new(
+                self.owned_data_change_broadcaster.subscribe(),
+            ),
+            pending: std::collections::VecDeque::new(),
+            lagged: 0,
+        }
+    }
+
+    /// Subscribes to value changes, coalescing changes of an item within `window`.
+    ///
+    /// Changes are collected per client handle and the latest value of every
+    /// changed item is yielded once per `window`, so each item updates at most
+    /// once per window. Items are resolved as in [`subscribe`](Self::subscribe).
+    /// A zero window is raised to one millisecond.
+    pub fn debounced_stream(&self, window: std::time::Duration) -> DebouncedUpdates {
+        DebouncedUpdates {
+            names: self
+                .items
+                .values()
+                .map(|item| (item.client_handle, item.name.clone()))
+                .collect(),
+            events: tokio_stream::wrappers::BroadcastStream::new(
+                self.owned_data_change_broadcaster.subscribe(),
+            ),
+            window: window.max(std::time::Duration::from_millis(1)),
+            interval: None,
+            latest: HashMap::new(),
+            order: vec![],
+            pending: std::collections::VecDeque::new(),
+            closed: false,
+            lagged: 0,
+        }
+    }
+
+    /// Waits until the server calls back with any data change.
+    ///
+    /// Subscribes immediately, so call it right after creating a subscription
+    /// to fail fast when the callback path is broken, typically by missing
+    /// DCOM callback permissions.
+    ///
+    /// # Errors
+    /// Returns `RPC_E_TIMEOUT` if no callback arrives within `timeout`.
+    pub fn await_first_callback(
+        &self,
+        timeout: std::time::Duration,
+    ) -> impl std::future::Future<Output = windows::core::Result<()>> + Send + 'static {
+        self.affinity.check("Group");
+        let mut receiver = self.owned_data_change_broadcaster.subscribe();
+
+        async move {
+            match tokio::time::timeout(timeout, receiver.recv()).await {
+                Ok(Ok(_)) | Ok(Err(tokio::sync::broadcast::error::RecvError::Lagged(_))) => Ok(()),
+                Ok(Err(tokio::sync::broadcast::error::RecvError::Closed)) => {
+                    Err(error::fail("Group dropped before any data callback"))
+                }
+                Err(_) => Err(windows::core::Error::new(
+                    windows::Win32::Foundation::RPC_E_TIMEOUT,
+                    "No data callback received within the timeout",
+                )),
+            }
+        }
+    }
+
+    /// Resolves the future awaiting `transaction_id`, if any.
+    ///
+    /// Subscription updates carry transaction id `0`, and callbacks of
+    /// requests issued outside this group have no awaiter; both are accepted.
     fn handle_callback<T>(
         &self,
         awaiters: &std::sync::Mutex<BTreeMap<u32, tokio::sync::oneshot::Sender<T>>>,
         transaction_id: u32,
         event: T,
     ) -> windows::core::Result<()> {
-        let mut awaiters = awaiters.lock().map_err(|_| {
-            windows_core::Error::new(windows::Win32::Foundation::E_FAIL, "lock poisoned")
-        })?;
+        if transaction_id == 0 {
+            return Ok(());
+        }
 
-        let awaiter = awaiters.remove(&transaction_id).ok_or_else(|| {
-            windows_core::Error::new(windows::Win32::Foundation::E_FAIL, "no awaiter found")
-        })?;
+        let mut awaiters = awaiters.lock().map_err(|_| error::fail("lock poisoned"))?;
 
-        awaiter.send(event).map_err(|_| {
-            windows_core::Error::new(windows::Win32::Foundation::E_FAIL, "event awaiter dropped")
-        })
+        let Some(awaiter) = awaiters.remove(&transaction_id) else {
+            return Ok(());
+        };
+
+        awaiter
+            .send(event)
+            .map_err(|_| error::fail("event awaiter dropped"))
     }
 
     fn next_receiver<T>(
@@ -122,9 +379,7 @@ impl Group {
 
         let (sender, receiver) = tokio::sync::oneshot::channel();
 
-        let mut awaiters = awaiters.lock().map_err(|_| {
-            windows_core::Error::new(windows::Win32::Foundation::E_FAIL, "lock poisoned")
-        })?;
+        let mut awaiters = awaiters.lock().map_err(|_| error::fail("lock poisoned"))?;
 
         awaiters.insert(transaction_id, sender);
 
@@ -134,16 +389,40 @@ impl Group {
 
 impl DataCallbackTrait for Group {
     fn on_data_change(&self, event: DataChangeEvent) -> windows_core::Result<()> {
-        self.data_change_broadcaster
-            .send(event.clone())
-            .map_err(|_| {
-                windows_core::Error::new(
-                    windows::Win32::Foundation::E_FAIL,
-                    "data change event receiver dropped",
-                )
-            })?;
+        if let Some(cache) = &self.value_cache {
+            for client_handle in event.client_items.as_slice() {
+                if let Some(item) = self
+                    .items
+                    .values()
+                    .find(|item| item.client_handle == *client_handle)
+                {
+                    cache.invalidate(&item.name);
+                }
+            }
+        }
+
+        if self.owned_data_change_broadcaster.receiver_count() > 0 {
+            match OwnedDataChangeEvent::try_from(&event) {
+                Ok(mut owned) => {
+                    self.filter_changes(&mut owned);
+                    if !owned.client_items.is_empty() {
+                        let _ = self.owned_data_change_broadcaster.send(owned);
+                    }
+                }
+                Err(e) => log::warn!("Failed to decode data change event: {e}"),
+            }
+        }
+
+        let transaction_id = event.transaction_id;
+        let result =
+            self.handle_callback(&self.data_change_awaiters, transaction_id, event.clone());
 
-        self.handle_callback(&self.data_change_awaiters, event.transaction_id, event)
+        // Raw receivers are optional, the event is simply dropped without one.
+        if self.data_change_broadcaster.receiver_count() > 0 {
+            let _ = self.data_change_broadcaster.send(event);
+        }
+
+        result
     }
 
     fn on_read_complete(&self, event: ReadCompleteEvent) -> windows_core::Result<()> {
@@ -169,14 +448,180 @@ impl Group {
         }
     }
 
+    #[inline(always)]
+    fn group_state_mgt(&self) -> &dyn GroupStateMgtTrait {
+        match &self.inner {
+            GroupInner::V1(group) => group,
+            GroupInner::V2(group) => group,
+            GroupInner::V3(group) => group,
+        }
+    }
+
+    /// Gets the current state of the group.
+    ///
+    /// The update rate is the one revised by the server, which may differ
+    /// from the rate requested when the group was added.
+    pub fn get_state(&self) -> windows::core::Result<GroupState> {
+        self.affinity.check("Group");
+        self.group_state_mgt().get_state()
+    }
+
+    /// Updates the fields of the group state that are `Some`.
+    ///
+    /// # Returns
+    /// The update rate revised by the server.
+    pub fn set_state(&self, update: GroupStateUpdate) -> windows::core::Result<u32> {
+        self.affinity.check("Group");
+        self.group_state_mgt().set_state(
+            update.update_rate,
+            update.active,
+            update.time_bias,
+            update.percent_deadband,
+            update.locale_id,
+            update.client_handle,
+        )
+    }
+
+    /// Requests a new update rate in milliseconds.
+    ///
+    /// # Returns
+    /// The update rate revised by the server.
+    pub fn set_update_rate(&self, requested_ms: u32) -> windows::core::Result<u32> {
+        self.set_state(GroupStateUpdate {
+            update_rate: Some(requested_ms),
+            ..Default::default()
+        })
+    }
+
+    /// Requests a new update rate and checks that the server honored it.
+    ///
+    /// # Errors
+    /// Returns `E_FAIL` if the revised rate differs from the requested one by
+    /// more than `tolerance_ms`. The revised rate stays in effect.
+    pub fn set_update_rate_checked(
+        &self,
+        requested_ms: u32,
+        tolerance_ms: u32,
+    ) -> windows::core::Result<u32> {
+        let revised = self.set_update_rate(requested_ms)?;
+        if revised.abs_diff(requested_ms) > tolerance_ms {
+            return Err(error::fail(format!(
+                "Server revised the update rate from {requested_ms} ms to {revised} ms"
+            )));
+        }
+
+        Ok(revised)
+    }
+
+    /// Makes the group public so that other clients can attach to it.
+    ///
+    /// # Errors
+    /// Returns E_NOTIMPL for V3 groups and groups without
+    /// `IOPCPublicGroupStateMgt`.
+    pub fn move_to_public(&self) -> windows::core::Result<()> {
+        self.affinity.check("Group");
+        match &self.inner {
+            GroupInner::V1(group) => group.move_to_public(),
+            GroupInner::V2(group) => group.move_to_public(),
+            GroupInner::V3(_) => Err(error::not_impl(
+                "IOPCPublicGroupStateMgt not supported for v3",
+            )),
+        }
+    }
+
+    /// Returns whether the group is public.
+    ///
+    /// # Errors
+    /// Returns E_NOTIMPL for V3 groups and groups without
+    /// `IOPCPublicGroupStateMgt`.
+    pub fn is_public(&self) -> windows::core::Result<bool> {
+        self.affinity.check("Group");
+        match &self.inner {
+            GroupInner::V1(group) => PublicGroupStateMgtTrait::get_state(group),
+            GroupInner::V2(group) => PublicGroupStateMgtTrait::get_state(group),
+            GroupInner::V3(_) => Err(error::not_impl(
+                "IOPCPublicGroupStateMgt not supported for v3",
+            )),
+        }
+    }
+
+    /// Captures the group state and its items, see
+    /// [`Server::create_group_from_spec`](super::Server::create_group_from_spec).
+    ///
+    /// Items are read back from the server with `IEnumOPCItemAttributes`, so
+    /// the spec keeps their requested data types and client handles.
+    pub fn to_spec(&self) -> windows::core::Result<SubscriptionSpec> {
+        self.affinity.check("Group");
+        let state = GroupState {
+            server_handle: 0,
+            ..self.get_state()?
+        };
+        let items = self
+            .item_mgt()
+            .create_enumerator()?
+            .map(|attributes| attributes.map(|attributes| ItemDef::from(&attributes)))
+            .collect::<windows::core::Result<_>>()?;
+
+        Ok(SubscriptionSpec { state, items })
+    }
+
+    /// Sets the number of items sent per `AddItems` call, see [`Group::add`].
+    ///
+    /// A size of 0 is raised to 1.
+    pub fn set_add_batch_size(&mut self, size: usize) {
+        self.add_batch_size = size.max(1);
+    }
+
+    /// Returns the number of items sent per `AddItems` call.
+    pub fn add_batch_size(&self) -> usize {
+        self.add_batch_size
+    }
+
+    /// Adds items to the group.
+    ///
+    /// Items are sent in batches of [`add_batch_size`](Self::add_batch_size)
+    /// because some servers fail or hang on very large `AddItems` calls. The
+    /// results are in the order of `items`.
+    ///
+    /// # Errors
+    /// Returns the error of the first batch if it fails as a whole. When a
+    /// later batch fails, the items added by earlier batches are kept and
+    /// every item of the failed batch reports the batch error.
     pub fn add(
         &self,
         items: Vec<ItemDef>,
     ) -> windows::core::Result<Vec<windows::core::Result<ItemResult>>> {
+        self.affinity.check("Group");
+        let mut results = Vec::with_capacity(items.len());
+        let mut items = items.into_iter().peekable();
+        while items.peek().is_some() {
+            let batch: Vec<_> = items.by_ref().take(self.add_batch_size).collect();
+            let batch_len = batch.len();
+            match self.add_batch(batch) {
+                Ok(batch_results) => results.extend(batch_results),
+                Err(error) if results.is_empty() => return Err(error),
+                Err(error) => {
+                    results.extend(std::iter::repeat_n(Err(error), batch_len));
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    fn add_batch(
+        &self,
+        items: Vec<ItemDef>,
+    ) -> windows::core::Result<Vec<windows::core::Result<ItemResult>>> {
+        let item_ids: Vec<String> = items.iter().map(|item| item.item_id.clone()).collect();
         let bridge = items.into_bridge();
-        self.item_mgt()
+        let results: Vec<_> = self
+            .item_mgt()
             .add_items(&bridge.try_to_native()?)?
-            .try_to_local()
+            .try_to_local()?;
+        self.record_canonical_types(&item_ids, &results);
+
+        Ok(results)
     }
 
     pub fn validate(
@@ -184,93 +629,713 @@ impl Group {
         items: Vec<ItemDef>,
         blob_update: bool,
     ) -> windows::core::Result<Vec<windows::core::Result<ItemResult>>> {
+        self.affinity.check("Group");
+        let item_ids: Vec<String> = items.iter().map(|item| item.item_id.clone()).collect();
         let bridge = items.into_bridge();
-        self.item_mgt()
+        let results: Vec<_> = self
+            .item_mgt()
             .validate_items(&bridge.try_to_native()?, blob_update)?
-            .try_to_local()
+            .try_to_local()?;
+        self.record_canonical_types(&item_ids, &results);
+
+        Ok(results)
+    }
+
+    /// Validates items with blob update, then adds them with the returned blobs.
+    ///
+    /// This is the OPC blob handshake: servers may return a blob from
+    /// `ValidateItems` that speeds up `AddItems` for the same item. Items that
+    /// fail validation are not added and report the validation error.
+    ///
+    /// # Returns
+    /// The add or validation result of each item, in the order of `items`
+    pub fn revalidate_and_add(
+        &self,
+        mut items: Vec<ItemDef>,
+    ) -> windows::core::Result<Vec<windows::core::Result<ItemResult>>> {
+        let validated = self.validate(items.clone(), true)?;
+        if validated.len() != items.len() {
+            return Err(error::fail(
+                "Server returned a result count differing from the items",
+            ));
+        }
+
+        let mut valid = Vec::with_capacity(items.len());
+        for (item, result) in items.iter_mut().zip(&validated) {
+            if let Ok(result) = result {
+                if !result.blob.is_empty() {
+                    item.blob = result.blob.clone();
+                }
+                valid.push(std::mem::take(item));
+            }
+        }
+
+        let mut added = if valid.is_empty() {
+            vec![]
+        } else {
+            self.add(valid)?
+        }
+        .into_iter();
+
+        validated
+            .into_iter()
+            .map(|result| match result {
+                Ok(_) => added
+                    .next()
+                    .ok_or_else(|| error::fail("Server returned fewer results than items")),
+                Err(error) => Ok(Err(error)),
+            })
+            .collect()
+    }
+
+    /// Adds all items or none of them.
+    ///
+    /// Unlike [`add`](Self::add), which keeps the items that succeeded, a
+    /// single failure removes every item this call added, so the group is
+    /// never left half configured.
+    ///
+    /// # Errors
+    /// Returns the error of the first item that failed, with the HRESULT the
+    /// server reported for it. Failures to roll back are logged.
+    pub fn add_items_atomic(&self, defs: Vec<ItemDef>) -> windows::core::Result<Vec<ItemResult>> {
+        let item_ids: Vec<String> = defs.iter().map(|item| item.item_id.clone()).collect();
+        let results = self.add(defs)?;
+
+        let Some((item_id, error)) = item_ids
+            .iter()
+            .zip(&results)
+            .find_map(|(item_id, result)| result.as_ref().err().map(|error| (item_id, error)))
+        else {
+            return results.into_iter().collect();
+        };
+
+        let added: Vec<u32> = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok().map(|result| result.server_handle))
+            .collect();
+        if !added.is_empty() {
+            match self.remove(added) {
+                Ok(removed) => {
+                    for error in removed.into_iter().filter_map(Result::err) {
+                        log::warn!("Failed to roll back an added item: {error}");
+                    }
+                }
+                Err(error) => log::warn!("Failed to roll back added items: {error}"),
+            }
+        }
+
+        Err(windows::core::Error::new(
+            error.code(),
+            format!(
+                "Failed to add {item_id}, no item was added: {}",
+                error.message()
+            ),
+        ))
+    }
+
+    /// Remembers the canonical types reported by `AddItems` or `ValidateItems`.
+    fn record_canonical_types(
+        &self,
+        item_ids: &[String],
+        results: &[windows::core::Result<ItemResult>],
+    ) {
+        let mut canonical_types = self
+            .canonical_types
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        for (item_id, result) in item_ids.iter().zip(results) {
+            if let Ok(result) = result {
+                canonical_types.insert(item_id.clone(), result.data_type);
+            }
+        }
+    }
+
+    /// Converts written values to the canonical type of their items.
+    ///
+    /// Off by default. When enabled, values written through `write_sync`,
+    /// `write_async` and `write_items_vqt` are converted with
+    /// [`Variant::coerce_to`] to the canonical type reported when the item was
+    /// added or validated, and a value that does not fit fails the write.
+    pub fn set_coerce_write_types(&mut self, coerce: bool) {
+        self.coerce_write_types = coerce;
+    }
+
+    /// Allows writing `VT_EMPTY` and `VT_NULL` values.
+    ///
+    /// Off by default, as servers either clear the item or fail on them, and
+    /// they usually come from an unset value. While off, such writes fail with
+    /// `E_INVALIDARG` before reaching the server.
+    pub fn set_allow_null_writes(&mut self, allow: bool) {
+        self.allow_null_writes = allow;
+    }
+
+    /// Filters the updates broadcast to [`subscribe`](Self::subscribe) and
+    /// the other decoded data change streams.
+    ///
+    /// Each item is compared with the last value broadcast for it, and an
+    /// event whose items are all insignificant is not sent. The raw
+    /// [`DataChangeEvent`] channel and pending refreshes are not filtered.
+    /// `None`, the default, broadcasts every update.
+    pub fn set_change_filter(&mut self, filter: Option<Box<dyn ChangeFilter>>) {
+        self.change_filter = filter;
+        self.broadcast_values
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clear();
+    }
+
+    /// Drops the items of `event` that the change filter deems insignificant.
+    fn filter_changes(&self, event: &mut OwnedDataChangeEvent) {
+        let Some(filter) = &self.change_filter else {
+            return;
+        };
+
+        let mut values = self
+            .broadcast_values
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let keep: Vec<bool> = (0..event.client_items.len())
+            .map(|index| {
+                let (Some(value), Some(quality), Some(timestamp)) = (
+                    event.values.get(index),
+                    event.qualities.get(index),
+                    event.timestamps.get(index),
+                ) else {
+                    return true;
+                };
+                if event.errors.get(index).is_some_and(|error| error.is_err()) {
+                    return true;
+                }
+
+                let next = ItemValue {
+                    value: value.clone().into(),
+                    quality: *quality,
+                    timestamp: *timestamp,
+                };
+                let client_handle = event.client_items[index];
+                let significant = values
+                    .get(&client_handle)
+                    .is_none_or(|prev| filter.is_significant(prev, &next));
+                if significant {
+                    values.insert(client_handle, next);
+                }
+
+                significant
+            })
+            .collect();
+
+        fn retain<T>(items: &mut Vec<T>, keep: &[bool]) {
+            let mut keep = keep.iter();
+            items.retain(|_| keep.next().copied().unwrap_or(true));
+        }
+        retain(&mut event.client_items, &keep);
+        retain(&mut event.values, &keep);
+        retain(&mut event.qualities, &keep);
+        retain(&mut event.timestamps, &keep);
+        retain(&mut event.errors, &keep);
+    }
+
+    /// Caps the async interface used by `read_async`, `write_async` and `refresh_async`.
+    ///
+    /// With the default of 3, 3.0 groups use `IOPCAsyncIO3`. Capping at 2
+    /// uses `IOPCAsyncIO2` instead, a workaround for servers whose
+    /// `ReadMaxAge` misbehaves; reads then always come from the device and
+    /// ignore the max age. Below 2 these calls fail with E_NOTIMPL.
+    pub fn prefer_async_version(&mut self, max: u8) {
+        self.max_async_version = max;
+    }
+
+    /// Returns the value to write for `name`, see [`set_coerce_write_types`](Self::set_coerce_write_types)
+    /// and [`set_allow_null_writes`](Self::set_allow_null_writes).
+    fn write_value(
+        &self,
+        name: &str,
+        mut value: ItemPartialValue,
+    ) -> windows::core::Result<ItemPartialValue> {
+        let vt = value.value.vt();
+        if !self.allow_null_writes
+            && (vt == windows::Win32::System::Variant::VT_EMPTY
+                || vt == windows::Win32::System::Variant::VT_NULL)
+        {
+            return Err(error::invalid_arg(format!(
+                "cannot write empty/null value to {name}"
+            )));
+        }
+
+        let canonical_type = match self.coerce_write_types {
+            true => self
+                .canonical_types
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .get(name)
+                .copied(),
+            false => None,
+        };
+        if let Some(canonical_type) = canonical_type {
+            let variant = Variant::from(&value.value);
+            // Only values that decode losslessly are converted, others are sent unchanged.
+            if variant.get_data_type() == value.value.vt().0 {
+                value.value = variant.coerce_to(canonical_type)?.into();
+            }
+        }
+
+        Ok(value)
     }
 
     pub fn remove(
         &self,
         server_handles: Vec<u32>,
     ) -> windows::core::Result<Vec<windows::core::Result<()>>> {
+        self.affinity.check("Group");
         self.item_mgt()
             .remove_items(&server_handles)?
             .try_to_local()
     }
 
-    // TODO set_active_state
-    // TODO set_client_handle
-    // TODO set_datatypes
-    // TODO create_enumerator
-
-    fn read_sync1<T: SyncIoTrait>(
-        &self,
+    /// Requests the data types values of the named items are delivered as.
+    ///
+    /// # Arguments
+    /// * `names` - Names of items in the group
+    /// * `types` - Requested `VARTYPE` for each item, `VT_EMPTY` for canonical
+    ///
+    /// # Returns
+    /// One result per item, failing where the server rejects the conversion.
+    ///
+    /// # Errors
+    /// Returns `E_INVALIDARG` if the lengths differ or a name is unknown.
+    pub fn set_data_types<S>(
+        &self,
+        names: &[S],
+        types: &[u16],
+    ) -> windows::core::Result<Vec<windows::core::Result<()>>>
+    where
+        S: AsRef<str>,
+    {
+        self.affinity.check("Group");
+        if names.len() != types.len() {
+            return Err(error::invalid_arg(
+                "names and types must have the same length",
+            ));
+        }
+
+        let server_handles: Vec<u32> = names
+            .iter()
+            .map(|name| {
+                self.items
+                    .get(name.as_ref())
+                    .map(|item| item.server_handle)
+                    .ok_or_else(|| error::invalid_arg("item name not found"))
+            })
+            .collect::<windows::core::Result<_>>()?;
+
+        self.item_mgt()
+            .set_datatypes(&server_handles, types)?
+            .try_to_local()
+    }
+
+    // TODO set_active_state
+    // TODO set_client_handle
+
+    fn read_sync1<T: SyncIoTrait>(
+        sync_io1: &T,
+        data_source: opc_da_bindings::tagOPCDATASOURCE,
+        server_handles: &[u32],
+    ) -> windows::core::Result<Vec<windows::core::Result<ItemValue>>> {
+        let mut values = Vec::with_capacity(server_handles.len());
+        Self::read_into1(sync_io1, data_source, server_handles, &mut values)?;
+        Ok(values)
+    }
+
+    fn read_sync2<T: SyncIo2Trait>(
+        sync_io2: &T,
+        server_handles: &[u32],
+        max_ages: &[u32],
+    ) -> windows::core::Result<Vec<windows::core::Result<ItemValue>>> {
+        let mut values = Vec::with_capacity(server_handles.len());
+        extend_item_values(
+            &mut values,
+            &mut sync_io2.read_max_age(server_handles, max_ages)?,
+        )?;
+        Ok(values)
+    }
+
+    /// Reads items synchronously.
+    ///
+    /// With the value cache enabled, `DataSourceTarget::WithMaxAge` is served
+    /// from values cached within the max age without a round-trip, also for
+    /// V1/V2 servers. Only the remaining items are read from the server.
+    pub fn read_sync<S>(
+        &self,
+        items_names: &[S],
+        data_source: DataSourceTarget,
+    ) -> windows::core::Result<Vec<windows::core::Result<ItemValue>>>
+    where
+        S: AsRef<str>,
+    {
+        self.affinity.check("Group");
+        let cache = match &self.value_cache {
+            Some(cache) => cache,
+            None => return self.read_sync_uncached(items_names, data_source),
+        };
+
+        let mut results: Vec<Option<windows::core::Result<ItemValue>>> = match data_source {
+            DataSourceTarget::WithMaxAge(max_age) => {
+                let max_age = std::time::Duration::from_millis(max_age as u64);
+                items_names
+                    .iter()
+                    .map(|name| cache.get(name.as_ref(), max_age).map(Ok))
+                    .collect()
+            }
+            _ => items_names.iter().map(|_| None).collect(),
+        };
+
+        let missing: Vec<&str> = items_names
+            .iter()
+            .zip(&results)
+            .filter(|(_, result)| result.is_none())
+            .map(|(name, _)| name.as_ref())
+            .collect();
+
+        if !missing.is_empty() {
+            // V1/V2 servers cannot honor a max age, so a stale cache falls back to the device.
+            let data_source = match (&self.inner, data_source) {
+                (GroupInner::V3(_), data_source) => data_source,
+                (_, DataSourceTarget::WithMaxAge(_)) => DataSourceTarget::ForceDevice,
+                (_, data_source) => data_source,
+            };
+
+            let mut fetched = self.read_sync_uncached(&missing, data_source)?.into_iter();
+
+            for (name, result) in items_names.iter().zip(results.iter_mut()) {
+                if result.is_some() {
+                    continue;
+                }
+
+                let value = fetched.next().ok_or_else(|| {
+                    windows::core::Error::new(
+                        windows::Win32::Foundation::E_UNEXPECTED,
+                        "server returned fewer values than requested",
+                    )
+                })?;
+
+                if let Ok(value) = &value {
+                    cache.insert(name.as_ref(), value);
+                }
+
+                *result = Some(value);
+            }
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    fn read_sync_uncached<S>(
+        &self,
+        items_names: &[S],
+        data_source: DataSourceTarget,
+    ) -> windows::core::Result<Vec<windows::core::Result<ItemValue>>>
+    where
+        S: AsRef<str>,
+    {
+        let server_handles: Vec<u32> = items_names
+            .iter()
+            .map(|name| {
+                self.items
+                    .get(name.as_ref())
+                    .map(|item| item.server_handle)
+                    .ok_or_else(|| error::invalid_arg("item name not found"))
+            })
+            .collect::<windows::core::Result<_>>()?;
+
+        match &self.inner {
+            GroupInner::V1(group) => {
+                Self::read_sync1(group, data_source.try_to_native()?, &server_handles)
+            }
+            GroupInner::V2(group) => {
+                Self::read_sync1(group, data_source.try_to_native()?, &server_handles)
+            }
+            GroupInner::V3(group) => Self::read_sync2(
+                group,
+                &server_handles,
+                &data_source.to_max_ages(server_handles.len()),
+            ),
+        }
+    }
+
+    /// Reads items synchronously into `out`, reusing its allocation.
+    ///
+    /// `out` is cleared first and holds one result per name afterwards, as
+    /// returned by [`read_sync`](Self::read_sync). Meant for tight polling
+    /// loops; the COM call still allocates its output arrays. With the value
+    /// cache enabled the read goes through `read_sync`.
+    ///
+    /// # Errors
+    /// On a call-level error `out` is left empty.
+    pub fn read_into<S>(
+        &self,
+        items_names: &[S],
+        data_source: DataSourceTarget,
+        out: &mut Vec<windows::core::Result<ItemValue>>,
+    ) -> windows::core::Result<()>
+    where
+        S: AsRef<str>,
+    {
+        self.affinity.check("Group");
+        out.clear();
+        if self.value_cache.is_some() {
+            out.extend(self.read_sync(items_names, data_source)?);
+            return Ok(());
+        }
+
+        let server_handles: Vec<u32> = items_names
+            .iter()
+            .map(|name| {
+                self.items
+                    .get(name.as_ref())
+                    .map(|item| item.server_handle)
+                    .ok_or_else(|| error::invalid_arg("item name not found"))
+            })
+            .collect::<windows::core::Result<_>>()?;
+
+        match &self.inner {
+            GroupInner::V1(group) => {
+                Self::read_into1(group, data_source.try_to_native()?, &server_handles, out)
+            }
+            GroupInner::V2(group) => {
+                Self::read_into1(group, data_source.try_to_native()?, &server_handles, out)
+            }
+            GroupInner::V3(group) => extend_item_values(
+                out,
+                &mut SyncIo2Trait::read_max_age(
+                    group,
+                    &server_handles,
+                    &data_source.to_max_ages(server_handles.len()),
+                )?,
+            ),
+        }
+    }
+
+    fn read_into1<T: SyncIoTrait>(
+        sync_io1: &T,
+        data_source: opc_da_bindings::tagOPCDATASOURCE,
+        server_handles: &[u32],
+        out: &mut Vec<windows::core::Result<ItemValue>>,
+    ) -> windows::core::Result<()> {
+        let (mut states, errors) = sync_io1.read(data_source, server_handles)?;
+        if states.len() != errors.len() {
+            return Err(windows::core::Error::new(
+                windows::Win32::Foundation::E_INVALIDARG,
+                "Results and errors arrays have different lengths",
+            ));
+        }
+
+        for (state, error) in states.as_mut_slice().iter_mut().zip(errors.as_slice()) {
+            // Take the value so that the VARIANT is not left behind in the array.
+            let value = core::mem::take(&mut state.vDataValue);
+            out.push(if error.is_ok() {
+                state.ftTimeStamp.try_to_local().map(|timestamp| ItemValue {
+                    value,
+                    quality: state.wQuality,
+                    timestamp,
+                })
+            } else {
+                Err((*error).into())
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Reads the quality and timestamp of items from the server cache.
+    ///
+    /// Meant for staleness monitoring: the values are freed right after the
+    /// call instead of being decoded, and the client-side value cache is not
+    /// used.
+    ///
+    /// # Returns
+    /// The quality and timestamp or the per-item error, in the order of `names`
+    pub fn read_qualities<S>(
+        &self,
+        names: &[S],
+    ) -> windows::core::Result<Vec<windows::core::Result<(u16, std::time::SystemTime)>>>
+    where
+        S: AsRef<str>,
+    {
+        self.affinity.check("Group");
+        let server_handles: Vec<u32> = names
+            .iter()
+            .map(|name| {
+                self.items
+                    .get(name.as_ref())
+                    .map(|item| item.server_handle)
+                    .ok_or_else(|| error::invalid_arg("item name not found"))
+            })
+            .collect::<windows::core::Result<_>>()?;
+
+        match &self.inner {
+            GroupInner::V1(group) => Self::read_qualities1(group, &server_handles),
+            GroupInner::V2(group) => Self::read_qualities1(group, &server_handles),
+            GroupInner::V3(group) => {
+                let (mut values, qualities, timestamps, errors) = SyncIo2Trait::read_max_age(
+                    group,
+                    &server_handles,
+                    &DataSourceTarget::ForceCache.to_max_ages(server_handles.len()),
+                )?;
+                values.clear_variants();
+
+                if qualities.len() != timestamps.len() || qualities.len() != errors.len() {
+                    return Err(error::invalid_arg("Arrays have different lengths"));
+                }
+
+                qualities
+                    .as_slice()
+                    .iter()
+                    .zip(timestamps.as_slice())
+                    .zip(errors.as_slice())
+                    .map(|((quality, timestamp), error)| {
+                        Ok(if error.is_ok() {
+                            Ok((*quality, timestamp.try_to_local()?))
+                        } else {
+                            Err((*error).into())
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    fn read_qualities1<T: SyncIoTrait>(
         sync_io1: &T,
-        data_source: DataSourceTarget,
         server_handles: &[u32],
-    ) -> windows::core::Result<Vec<windows::core::Result<ItemValue>>> {
-        let results: Vec<windows::core::Result<ItemState>> = sync_io1
-            .read(data_source.try_to_native()?, server_handles)?
-            .try_to_local()?;
+    ) -> windows::core::Result<Vec<windows::core::Result<(u16, std::time::SystemTime)>>> {
+        let (mut states, errors) = sync_io1.read(
+            DataSourceTarget::ForceCache.try_to_native()?,
+            server_handles,
+        )?;
+        for state in states.as_mut_slice() {
+            // Nothing useful can be done with a failure, the value is discarded anyway.
+            let _ = unsafe { windows::Win32::System::Variant::VariantClear(&mut state.vDataValue) };
+        }
 
-        Ok(results
-            .into_iter()
-            .map(|r| {
-                r.map(|r| ItemValue {
-                    value: r.data_value,
-                    quality: r.quality,
-                    timestamp: r.timestamp,
+        if states.len() != errors.len() {
+            return Err(error::invalid_arg(
+                "Results and errors arrays have different lengths",
+            ));
+        }
+
+        states
+            .as_slice()
+            .iter()
+            .zip(errors.as_slice())
+            .map(|(state, error)| {
+                Ok(if error.is_ok() {
+                    Ok((state.wQuality, state.ftTimeStamp.try_to_local()?))
+                } else {
+                    Err((*error).into())
                 })
             })
-            .collect())
+            .collect()
     }
 
-    fn read_sync2<T: SyncIo2Trait>(
-        &self,
-        sync_io2: &T,
-        server_handles: &[u32],
-        max_ages: &[u32],
-    ) -> windows::core::Result<Vec<windows::core::Result<ItemValue>>> {
-        sync_io2
-            .read_max_age(server_handles, max_ages)?
-            .try_to_local()
-    }
-
-    pub fn read_sync<S>(
+    /// Reads items synchronously, giving up after `timeout`.
+    ///
+    /// The read runs on a dedicated thread in the multithreaded apartment,
+    /// through a proxy unmarshalled from the global interface table, so the
+    /// group may belong to either kind of apartment. If the read does not
+    /// complete in time, `RPC_E_TIMEOUT` is returned and the result is
+    /// discarded. COM calls cannot be cancelled, so the abandoned thread may
+    /// stay blocked until the server answers or the call fails, but the
+    /// caller regains control.
+    pub fn read_items_sync_timeout<S>(
         &self,
         items_names: &[S],
         data_source: DataSourceTarget,
+        timeout: std::time::Duration,
     ) -> windows::core::Result<Vec<windows::core::Result<ItemValue>>>
     where
         S: AsRef<str>,
     {
+        self.affinity.check("Group");
         let server_handles: Vec<u32> = items_names
             .iter()
             .map(|name| {
                 self.items
                     .get(name.as_ref())
                     .map(|item| item.server_handle)
-                    .ok_or_else(|| {
-                        windows::core::Error::new(
-                            windows::Win32::Foundation::E_INVALIDARG,
-                            "item name not found",
-                        )
-                    })
+                    .ok_or_else(|| error::invalid_arg("item name not found"))
             })
             .collect::<windows::core::Result<_>>()?;
 
-        match &self.inner {
-            GroupInner::V1(group) => self.read_sync1(group, data_source, &server_handles),
-            GroupInner::V2(group) => self.read_sync1(group, data_source, &server_handles),
-            GroupInner::V3(group) => self.read_sync2(
-                group,
-                &server_handles,
-                &vec![data_source.max_age(); server_handles.len()],
-            ),
+        // `VARIANT`s cannot cross threads, the worker sends decoded values back.
+        type SentValue = (Variant, u16, std::time::SystemTime);
+        type Read = Box<
+            dyn FnOnce() -> windows::core::Result<Vec<windows::core::Result<ItemValue>>> + Send,
+        >;
+
+        let read: Read = match &self.inner {
+            GroupInner::V1(v1::Group { sync_io, .. })
+            | GroupInner::V2(v2::Group { sync_io, .. }) => {
+                let sync_io = GlobalInterface::register(sync_io)?;
+                let data_source = data_source.try_to_native()?;
+                Box::new(move || Self::read_sync1(&sync_io.get()?, data_source, &server_handles))
+            }
+            GroupInner::V3(group) => {
+                let sync_io2 = GlobalInterface::register(&group.sync_io2)?;
+                let max_ages = data_source.to_max_ages(server_handles.len());
+                Box::new(move || Self::read_sync2(&sync_io2.get()?, &server_handles, &max_ages))
+            }
+        };
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let result = Guard::<()>::try_initialize().and_then(|_| {
+                // Calling `read` drops the registration before COM is uninitialized.
+                let result = read().map(|values| {
+                    values
+                        .into_iter()
+                        .map(|value| {
+                            value.map(|value| -> SentValue {
+                                (value.value.into(), value.quality, value.timestamp)
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                });
+                Guard::<()>::uninitialize();
+                result
+            });
+            let _ = sender.send(result);
+        });
+
+        match receiver.recv_timeout(timeout) {
+            Ok(result) => result.map(|values| {
+                values
+                    .into_iter()
+                    .map(|value| {
+                        value.map(|(value, quality, timestamp)| ItemValue {
+                            value: value.into(),
+                            quality,
+                            timestamp,
+                        })
+                    })
+                    .collect()
+            }),
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => Err(windows::core::Error::new(
+                windows::Win32::Foundation::RPC_E_TIMEOUT,
+                "synchronous read timed out",
+            )),
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                Err(error::fail("synchronous read thread panicked"))
+            }
         }
     }
 
+    fn async_disabled(&self) -> windows::core::Error {
+        error::not_impl(format!(
+            "async I/O capped at version {} by prefer_async_version",
+            self.max_async_version
+        ))
+    }
+
     fn read_async2<T: AsyncIo2Trait>(
         &self,
         async_io2: &T,
@@ -325,31 +1390,28 @@ impl Group {
         DataCallbackFuture<ReadCompleteEvent>,
         Vec<windows::core::Result<()>>,
     )> {
+        self.affinity.check("Group");
         let server_handles: Vec<u32> = items_names
             .iter()
             .map(|name| {
                 self.items
                     .get(name.as_ref())
                     .map(|item| item.server_handle)
-                    .ok_or_else(|| {
-                        windows::core::Error::new(
-                            windows::Win32::Foundation::E_INVALIDARG,
-                            "item name not found",
-                        )
-                    })
+                    .ok_or_else(|| error::invalid_arg("item name not found"))
             })
             .collect::<windows::core::Result<_>>()?;
 
         match &self.inner {
-            GroupInner::V1(_) => Err(windows_core::Error::new(
-                windows::Win32::Foundation::E_NOTIMPL,
-                "read_async not implemented for v1",
-            )),
+            GroupInner::V1(_) => Err(error::not_impl("read_async not implemented for v1")),
+            _ if self.max_async_version < 2 => Err(self.async_disabled()),
             GroupInner::V2(group) => self.read_async2(group, &server_handles),
+            GroupInner::V3(group) if self.max_async_version < 3 => {
+                self.read_async2(group, &server_handles)
+            }
             GroupInner::V3(group) => self.read_async3(
                 group,
                 &server_handles,
-                &vec![data_source.max_age(); server_handles.len()],
+                &data_source.to_max_ages(server_handles.len()),
             ),
         }
     }
@@ -385,38 +1447,162 @@ impl Group {
     where
         S: AsRef<str>,
     {
+        self.write_sync_owned(
+            item_entities
+                .iter()
+                .map(|(name, value)| (name.as_ref(), value.clone()))
+                .collect(),
+        )
+    }
+
+    /// Writes values the caller no longer needs, without copying them again.
+    fn write_sync_owned(
+        &self,
+        item_entities: Vec<(&str, ItemPartialValue)>,
+    ) -> windows::core::Result<Vec<windows::core::Result<()>>> {
+        self.affinity.check("Group");
         let server_handles: Vec<u32> = item_entities
             .iter()
             .map(|(name, _)| {
                 self.items
-                    .get(name.as_ref())
+                    .get(*name)
                     .map(|item| item.server_handle)
-                    .ok_or_else(|| {
-                        windows::core::Error::new(
-                            windows::Win32::Foundation::E_INVALIDARG,
-                            "item name not found",
-                        )
-                    })
+                    .ok_or_else(|| error::invalid_arg("item name not found"))
             })
             .collect::<windows::core::Result<_>>()?;
 
-        let variants = item_entities.iter().map(|(_, value)| value.value.clone());
-
-        let item_values = item_entities.iter().map(|(_, value)| value.try_to_native());
+        let values = item_entities
+            .into_iter()
+            .map(|(name, value)| self.write_value(name, value))
+            .collect::<windows::core::Result<Vec<_>>>()?;
 
         match &self.inner {
             GroupInner::V1(group) => {
-                self.write_sync1(group, &server_handles, &variants.collect::<Vec<_>>())
+                self.write_sync1(group, &server_handles, &write_variants(values))
             }
             GroupInner::V2(group) => {
-                self.write_sync1(group, &server_handles, &variants.collect::<Vec<_>>())
+                self.write_sync1(group, &server_handles, &write_variants(values))
             }
-            GroupInner::V3(group) => self.write_sync2(
-                group,
-                &server_handles,
-                &item_values.collect::<windows::core::Result<Vec<_>>>()?,
-            ),
+            GroupInner::V3(group) => self.write_sync2(group, &server_handles, &write_vqts(values)?),
+        }
+    }
+
+    /// Reads items synchronously, see [`read_sync`](Self::read_sync).
+    ///
+    /// The per-item results are wrapped in [`ReadResults`] for easier access.
+    pub fn read_sync_results<S>(
+        &self,
+        items_names: &[S],
+        data_source: DataSourceTarget,
+    ) -> windows::core::Result<ReadResults>
+    where
+        S: AsRef<str>,
+    {
+        self.read_sync(items_names, data_source).map(ReadResults)
+    }
+
+    /// Reads a single item from the device.
+    ///
+    /// Convenience wrapper around [`read_sync`](Self::read_sync) that maps
+    /// the per-item error to the returned `Result`.
+    pub fn read(&self, name: &str) -> windows::core::Result<ItemValue> {
+        self.read_sync(&[name], DataSourceTarget::ForceDevice)?
+            .pop()
+            .ok_or_else(|| error::fail("Server returned no result for the item"))?
+    }
+
+    /// Writes a single value without quality or timestamp.
+    ///
+    /// Convenience wrapper around [`write_sync`](Self::write_sync) that maps
+    /// the per-item error to the returned `Result`.
+    pub fn write(&self, name: &str, value: Variant) -> windows::core::Result<()> {
+        let value = ItemPartialValue {
+            value: value.into(),
+            quality: None,
+            timestamp: None,
+        };
+
+        self.write_sync(&[(name, value)])?
+            .pop()
+            .ok_or_else(|| error::fail("Server returned no result for the item"))?
+    }
+
+    /// Writes plain values, converting strings through `cache`.
+    ///
+    /// Behaves like [`write_sync`](Self::write_sync) without quality or
+    /// timestamp. Reuse the cache across batches where string values repeat,
+    /// see [`StringWriteCache`].
+    pub fn write_items<S>(
+        &self,
+        items: &[(S, Variant)],
+        cache: &mut StringWriteCache,
+    ) -> windows::core::Result<Vec<windows::core::Result<()>>>
+    where
+        S: AsRef<str>,
+    {
+        let values: Vec<_> = items
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.as_ref(),
+                    ItemPartialValue {
+                        value: cache.to_variant(value),
+                        quality: None,
+                        timestamp: None,
+                    },
+                )
+            })
+            .collect();
+
+        self.write_sync_owned(values)
+    }
+
+    /// Writes values with their quality and timestamp.
+    ///
+    /// Unlike [`write_sync`](Self::write_sync), quality and timestamp are never
+    /// dropped: `None` fields are sent as unspecified, and servers without
+    /// `IOPCSyncIO2` are rejected.
+    ///
+    /// # Errors
+    /// Returns `E_NOTIMPL` for V1/V2 servers and `E_INVALIDARG` if the lengths
+    /// differ or a name is unknown.
+    pub fn write_items_vqt<S>(
+        &self,
+        names: &[S],
+        vqts: Vec<ItemPartialValue>,
+    ) -> windows::core::Result<Vec<windows::core::Result<()>>>
+    where
+        S: AsRef<str>,
+    {
+        self.affinity.check("Group");
+        if names.len() != vqts.len() {
+            return Err(error::invalid_arg(
+                "names and vqts must have the same length",
+            ));
         }
+
+        let group = match &self.inner {
+            GroupInner::V3(group) => group,
+            _ => return Err(error::not_impl("write_items_vqt requires OPC DA 3.0")),
+        };
+
+        let server_handles: Vec<u32> = names
+            .iter()
+            .map(|name| {
+                self.items
+                    .get(name.as_ref())
+                    .map(|item| item.server_handle)
+                    .ok_or_else(|| error::invalid_arg("item name not found"))
+            })
+            .collect::<windows::core::Result<_>>()?;
+
+        let values = names
+            .iter()
+            .zip(vqts)
+            .map(|(name, vqt)| self.write_value(name.as_ref(), vqt))
+            .collect::<windows::core::Result<Vec<_>>>()?;
+
+        self.write_sync2(group, &server_handles, &write_vqts(values)?)
     }
 
     fn write_async2<T: AsyncIo2Trait>(
@@ -434,9 +1620,10 @@ impl Group {
 
         let (sender, receive) = tokio::sync::oneshot::channel();
 
-        let mut awaiters = self.write_complete_awaiters.lock().map_err(|_| {
-            windows_core::Error::new(windows::Win32::Foundation::E_FAIL, "lock poisoned")
-        })?;
+        let mut awaiters = self
+            .write_complete_awaiters
+            .lock()
+            .map_err(|_| error::fail("lock poisoned"))?;
 
         awaiters.insert(transaction_id, sender);
 
@@ -486,38 +1673,34 @@ impl Group {
     where
         S: AsRef<str>,
     {
+        self.affinity.check("Group");
         let server_handles: Vec<u32> = item_entities
             .iter()
             .map(|(name, _)| {
                 self.items
                     .get(name.as_ref())
                     .map(|item| item.server_handle)
-                    .ok_or_else(|| {
-                        windows::core::Error::new(
-                            windows::Win32::Foundation::E_INVALIDARG,
-                            "item name not found",
-                        )
-                    })
+                    .ok_or_else(|| error::invalid_arg("item name not found"))
             })
             .collect::<windows::core::Result<_>>()?;
 
-        let variants = item_entities.iter().map(|(_, value)| value.value.clone());
-
-        let item_values = item_entities.iter().map(|(_, value)| value.try_to_native());
+        let values = item_entities
+            .iter()
+            .map(|(name, value)| self.write_value(name.as_ref(), value.clone()))
+            .collect::<windows::core::Result<Vec<_>>>()?;
 
         match &self.inner {
-            GroupInner::V1(_) => Err(windows_core::Error::new(
-                windows::Win32::Foundation::E_NOTIMPL,
-                "write_async not implemented for v1",
-            )),
+            GroupInner::V1(_) => Err(error::not_impl("write_async not implemented for v1")),
+            _ if self.max_async_version < 2 => Err(self.async_disabled()),
             GroupInner::V2(group) => {
-                self.write_async2(group, &server_handles, &variants.collect::<Vec<_>>())
+                self.write_async2(group, &server_handles, &write_variants(values))
+            }
+            GroupInner::V3(group) if self.max_async_version < 3 => {
+                self.write_async2(group, &server_handles, &write_variants(values))
+            }
+            GroupInner::V3(group) => {
+                self.write_async3(group, &server_handles, &write_vqts(values)?)
             }
-            GroupInner::V3(group) => self.write_async3(
-                group,
-                &server_handles,
-                &item_values.collect::<windows::core::Result<Vec<_>>>()?,
-            ),
         }
     }
 
@@ -528,9 +1711,10 @@ impl Group {
     ) -> windows::core::Result<DataCallbackFuture<CancelCompleteEvent>> {
         let (sender, receiver) = tokio::sync::oneshot::channel();
 
-        let mut awaiters = self.cancel_complete_awaiters.lock().map_err(|_| {
-            windows_core::Error::new(windows::Win32::Foundation::E_FAIL, "lock poisoned")
-        })?;
+        let mut awaiters = self
+            .cancel_complete_awaiters
+            .lock()
+            .map_err(|_| error::fail("lock poisoned"))?;
 
         awaiters.insert(cancel_id, sender);
 
@@ -547,11 +1731,9 @@ impl Group {
         &self,
         cancel_id: u32,
     ) -> windows::core::Result<DataCallbackFuture<CancelCompleteEvent>> {
+        self.affinity.check("Group");
         match &self.inner {
-            GroupInner::V1(_) => Err(windows_core::Error::new(
-                windows::Win32::Foundation::E_NOTIMPL,
-                "cancel_async not implemented for v1",
-            )),
+            GroupInner::V1(_) => Err(error::not_impl("cancel_async not implemented for v1")),
             GroupInner::V2(group) => self.cancel_async2(group, cancel_id),
             GroupInner::V3(group) => self.cancel_async2(group, cancel_id),
         }
@@ -593,32 +1775,562 @@ impl Group {
         &self,
         data_source: DataSourceTarget,
     ) -> windows::core::Result<DataCallbackFuture<DataChangeEvent>> {
+        self.affinity.check("Group");
         match &self.inner {
-            GroupInner::V1(_) => Err(windows::core::Error::new(
-                windows::Win32::Foundation::E_NOTIMPL,
-                "refresh not implemented for v1",
-            )),
+            GroupInner::V1(_) => Err(error::not_impl("refresh not implemented for v1")),
+            _ if self.max_async_version < 2 => Err(self.async_disabled()),
             GroupInner::V2(group) => self.refresh2_async(group, data_source),
+            GroupInner::V3(group) if self.max_async_version < 3 => {
+                self.refresh2_async(group, data_source)
+            }
             GroupInner::V3(group) => self.refresh3_async(group, data_source),
         }
     }
+
+    /// Requests a full data-change callback for every active item.
+    ///
+    /// This is the usual way to get an initial snapshot of a subscription:
+    /// the server answers with a single `OnDataChange` carrying the current
+    /// value of all active items, and the returned future resolves with it.
+    /// Uses `IOPCAsyncIO3::RefreshMaxAge` on 3.0 servers and
+    /// `IOPCAsyncIO2::Refresh2` on 2.0 servers, see
+    /// [`prefer_async_version`](Self::prefer_async_version).
+    ///
+    /// # Errors
+    /// Returns E_FAIL if the data callback is not advised yet, as the future
+    /// could never resolve, see [`initialize`](Self::initialize).
+    pub fn refresh(
+        &self,
+        source: DataSourceTarget,
+    ) -> windows::core::Result<DataCallbackFuture<DataChangeEvent>> {
+        if self.data_callback_cookie.is_none() {
+            return Err(error::fail(
+                "Data callback not advised, initialize the group before refreshing",
+            ));
+        }
+
+        self.refresh_async(source)
+    }
+}
+
+impl Group {
+    /// Subscribes to value changes by polling, for every server version.
+    ///
+    /// A background thread reads the active items of the group from the cache
+    /// with `IOPCSyncIO::Read` every `interval`, compares the values and
+    /// qualities with the previous read and broadcasts only the changed items.
+    /// The first read reports every item. Items added or activated after this
+    /// call are not polled. The channel holds as many events as the data
+    /// change channels, see [`with_broadcast_capacity`](Self::with_broadcast_capacity).
+    ///
+    /// The thread reads through its own proxy, unmarshalled from the global
+    /// interface table, so the group may belong to either kind of apartment.
+    /// The registration keeps the server group alive until the thread exits,
+    /// once every receiver has been dropped.
+    ///
+    /// A failed read is broadcast as an error and polling goes on with the
+    /// next interval. If the thread cannot join COM or unmarshal its proxy,
+    /// it broadcasts the error and stops, which closes the channel.
+    ///
+    /// # Errors
+    /// Returns the error of reading the group state or enumerating its items.
+    pub fn poll_changes(
+        &self,
+        interval: std::time::Duration,
+    ) -> windows::core::Result<
+        tokio::sync::broadcast::Receiver<windows::core::Result<OwnedDataChangeEvent>>,
+    > {
+        self.affinity.check("Group");
+        let (sender, receiver) = tokio::sync::broadcast::channel(self.broadcast_capacity);
+
+        let sync_io = match &self.inner {
+            GroupInner::V1(group) => &group.sync_io,
+            GroupInner::V2(group) => &group.sync_io,
+            GroupInner::V3(group) => &group.sync_io,
+        };
+        let poller = Poller {
+            sync_io: GlobalInterface::register(sync_io)?,
+            group_handle: self.group_state_mgt().get_state()?.client_handle,
+            items: self
+                .item_mgt()
+                .create_enumerator()?
+                .filter(|attributes| attributes.as_ref().map_or(true, |item| item.active))
+                .map(|attributes| attributes.map(|item| (item.server_handle, item.client_handle)))
+                .collect::<windows::core::Result<_>>()?,
+            last: HashMap::new(),
+        };
+
+        if poller.items.is_empty() {
+            return Ok(receiver);
+        }
+
+        std::thread::spawn(move || poller.run(sender, interval));
+
+        Ok(receiver)
+    }
+}
+
+impl SyncIoTrait for opc_da_bindings::IOPCSyncIO {
+    fn interface(&self) -> windows::core::Result<&opc_da_bindings::IOPCSyncIO> {
+        Ok(self)
+    }
+}
+
+impl SyncIo2Trait for opc_da_bindings::IOPCSyncIO2 {
+    fn interface(&self) -> windows::core::Result<&opc_da_bindings::IOPCSyncIO2> {
+        Ok(self)
+    }
+}
+
+type PolledValue = Result<(Variant, u16), windows::core::HRESULT>;
+
+struct Poller {
+    sync_io: GlobalInterface<opc_da_bindings::IOPCSyncIO>,
+    group_handle: u32,
+    items: Vec<(u32, u32)>,
+    last: HashMap<u32, PolledValue>,
+}
+
+impl Poller {
+    fn run(
+        mut self,
+        sender: tokio::sync::broadcast::Sender<windows::core::Result<OwnedDataChangeEvent>>,
+        interval: std::time::Duration,
+    ) {
+        if let Err(error) = Guard::<()>::try_initialize() {
+            log::warn!("Failed to initialize COM for polling: {error}");
+            let _ = sender.send(Err(error));
+            return;
+        }
+
+        match self.sync_io.get() {
+            Ok(sync_io) => {
+                while sender.receiver_count() > 0 {
+                    // Unchanged items yield no event, failures are sent as errors.
+                    if let Some(event) = self.poll(&sync_io).transpose()
+                        && sender.send(event).is_err()
+                    {
+                        break;
+                    }
+
+                    std::thread::sleep(interval);
+                }
+            }
+            Err(error) => {
+                log::warn!("Failed to unmarshal the group for polling: {error}");
+                let _ = sender.send(Err(error));
+            }
+        }
+
+        // Release the proxy and the registration before leaving the apartment.
+        drop(self);
+        Guard::<()>::uninitialize();
+    }
+
+    fn poll(
+        &mut self,
+        sync_io: &opc_da_bindings::IOPCSyncIO,
+    ) -> windows::core::Result<Option<OwnedDataChangeEvent>> {
+        let server_handles: Vec<u32> = self.items.iter().map(|(server, _)| *server).collect();
+        let (mut states, errors) = sync_io.read(opc_da_bindings::OPC_DS_CACHE, &server_handles)?;
+
+        let mut event = OwnedDataChangeEvent {
+            transaction_id: 0,
+            group_handle: self.group_handle,
+            client_items: vec![],
+            values: vec![],
+            qualities: vec![],
+            timestamps: vec![],
+            errors: vec![],
+        };
+
+        for ((state, error), (server_handle, client_handle)) in states
+            .as_mut_slice()
+            .iter_mut()
+            .zip(errors.as_slice())
+            .zip(&self.items)
+        {
+            // Take the value so that the VARIANT is cleared after decoding.
+            let value = Variant::from(core::mem::take(&mut state.vDataValue));
+            let polled = if error.is_ok() {
+                Ok((value, state.wQuality))
+            } else {
+                Err(*error)
+            };
+
+            if self.last.get(server_handle) == Some(&polled) {
+                continue;
+            }
+
+            let timestamp = state
+                .ftTimeStamp
+                .try_to_local()
+                .unwrap_or_else(|_| std::time::SystemTime::now());
+
+            event.client_items.push(*client_handle);
+            match &polled {
+                Ok((value, quality)) => {
+                    event.values.push(value.clone());
+                    event.qualities.push(*quality);
+                    event.errors.push(windows::Win32::Foundation::S_OK);
+                }
+                Err(error) => {
+                    event.values.push(Variant::Empty);
+                    event.qualities.push(opc_da_bindings::OPC_QUALITY_BAD);
+                    event.errors.push(*error);
+                }
+            }
+            event.timestamps.push(timestamp);
+
+            self.last.insert(*server_handle, polled);
+        }
+
+        if event.client_items.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(event))
+        }
+    }
 }
 
 impl From<v1::Group> for Group {
     fn from(group: v1::Group) -> Self {
-        Self::new(GroupInner::V1(group))
+        Self::new(GroupInner::V1(group), None)
     }
 }
 
 impl From<v2::Group> for Group {
     fn from(group: v2::Group) -> Self {
-        Self::new(GroupInner::V2(group))
+        Self::new(GroupInner::V2(group), None)
     }
 }
 
 impl From<v3::Group> for Group {
     fn from(group: v3::Group) -> Self {
-        Self::new(GroupInner::V3(group))
+        Self::new(GroupInner::V3(group), None)
+    }
+}
+
+impl Drop for Group {
+    fn drop(&mut self) {
+        // The advised callback points at this group, the server must not call
+        // it once the group is gone.
+        if let Some(cookie) = self.data_callback_cookie.take() {
+            let connection_point = match &self.inner {
+                GroupInner::V1(_) => None,
+                GroupInner::V2(group) => group.data_callback_connection_point().ok(),
+                GroupInner::V3(group) => group.data_callback_connection_point().ok(),
+            };
+            match connection_point {
+                Some(connection_point) => {
+                    if let Err(error) = unsafe { connection_point.Unadvise(cookie) } {
+                        log::warn!("Failed to unadvise data callback: {error}");
+                    }
+                }
+                None => log::warn!("Failed to unadvise data callback: no connection point"),
+            }
+        }
+    }
+}
+
+/// Settings of a group created with [`Server::add_group`](super::Server::add_group).
+#[derive(Debug, Clone, Default)]
+pub struct GroupBuilder {
+    pub(crate) state: GroupState,
+    pub(crate) value_cache_ttl: Option<std::time::Duration>,
+    pub(crate) broadcast_capacity: Option<usize>,
+    pub(crate) add_batch_size: Option<usize>,
+    pub(crate) coerce_write_types: bool,
+    pub(crate) allow_null_writes: bool,
+}
+
+impl GroupBuilder {
+    /// Creates an active group named `name`, an empty name lets the server pick one.
+    pub fn new(name: &str) -> Self {
+        Self {
+            state: GroupState {
+                name: name.to_string(),
+                active: true,
+                ..Default::default()
+            },
+            value_cache_ttl: None,
+            broadcast_capacity: None,
+            add_batch_size: None,
+            coerce_write_types: false,
+            allow_null_writes: false,
+        }
+    }
+
+    pub fn active(mut self, active: bool) -> Self {
+        self.state.active = active;
+        self
+    }
+
+    /// Requested update rate in milliseconds, the server may revise it.
+    pub fn update_rate(mut self, update_rate: u32) -> Self {
+        self.state.update_rate = update_rate;
+        self
+    }
+
+    pub fn client_handle(mut self, client_handle: u32) -> Self {
+        self.state.client_handle = client_handle;
+        self
+    }
+
+    pub fn time_bias(mut self, time_bias: i32) -> Self {
+        self.state.time_bias = time_bias;
+        self
+    }
+
+    pub fn percent_deadband(mut self, percent_deadband: f32) -> Self {
+        self.state.percent_deadband = percent_deadband;
+        self
+    }
+
+    pub fn locale_id(mut self, locale_id: u32) -> Self {
+        self.state.locale_id = locale_id;
+        self
+    }
+
+    /// Enables the client-side value cache, see [`Group::new`].
+    pub fn value_cache_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.value_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Sizes the data change channels, see [`Group::with_broadcast_capacity`].
+    pub fn broadcast_capacity(mut self, capacity: usize) -> Self {
+        self.broadcast_capacity = Some(capacity);
+        self
+    }
+
+    /// Limits the items sent per `AddItems` call, see [`Group::set_add_batch_size`].
+    pub fn add_batch_size(mut self, size: usize) -> Self {
+        self.add_batch_size = Some(size);
+        self
+    }
+
+    /// Converts written values to the canonical item types, see
+    /// [`Group::set_coerce_write_types`].
+    pub fn coerce_write_types(mut self, coerce: bool) -> Self {
+        self.coerce_write_types = coerce;
+        self
+    }
+
+    /// Allows writing empty and null values, see [`Group::set_allow_null_writes`].
+    pub fn allow_null_writes(mut self, allow: bool) -> Self {
+        self.allow_null_writes = allow;
+        self
+    }
+}
+
+impl From<GroupState> for GroupBuilder {
+    fn from(state: GroupState) -> Self {
+        Self {
+            state,
+            value_cache_ttl: None,
+            broadcast_capacity: None,
+            add_batch_size: None,
+            coerce_write_types: false,
+            allow_null_writes: false,
+        }
+    }
+}
+
+/// Event yielded by [`Group::data_change_stream`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubscriptionEvent {
+    /// A data change from the server.
+    Change(OwnedDataChangeEvent),
+    /// The subscriber fell behind and this many events were dropped.
+    Lagged(u64),
+}
+
+/// Stream of `(name, value)` updates returned by [`Group::subscribe`].
+pub struct ItemUpdates {
+    names: HashMap<u32, String>,
+    events: tokio_stream::wrappers::BroadcastStream<OwnedDataChangeEvent>,
+    pending: std::collections::VecDeque<(String, ItemValue)>,
+    lagged: u64,
+}
+
+impl ItemUpdates {
+    /// Returns the number of data change events dropped because the
+    /// subscriber fell behind, since subscribing.
+    pub fn lagged(&self) -> u64 {
+        self.lagged
+    }
+
+    fn push(&mut self, event: OwnedDataChangeEvent) {
+        let updates = decode_updates(&self.names, event)
+            .into_iter()
+            .map(|(_, update)| update);
+
+        self.pending.extend(updates);
+    }
+}
+
+/// Pairs the successful values of `event` with their client handle and name,
+/// skipping items without a known name.
+/// Moves the values out for `IOPCSyncIO::Write` and `IOPCAsyncIO2::Write`.
+fn write_variants(values: Vec<ItemPartialValue>) -> Vec<windows::Win32::System::Variant::VARIANT> {
+    values.into_iter().map(|value| value.value).collect()
+}
+
+/// Moves the values out for the OPC DA 3.0 `WriteVQT` methods.
+fn write_vqts(
+    values: Vec<ItemPartialValue>,
+) -> windows::core::Result<Vec<opc_da_bindings::tagOPCITEMVQT>> {
+    values
+        .into_iter()
+        .map(|value| item_vqt_to_native(value.value, value.quality, value.timestamp))
+        .collect()
+}
+
+fn decode_updates(
+    names: &HashMap<u32, String>,
+    event: OwnedDataChangeEvent,
+) -> Vec<(u32, (String, ItemValue))> {
+    event
+        .client_items
+        .iter()
+        .zip(event.values)
+        .zip(event.qualities.iter().zip(&event.timestamps))
+        .zip(&event.errors)
+        .filter(|(_, error)| error.is_ok())
+        .filter_map(|(((client_handle, value), (quality, timestamp)), _)| {
+            let name = names.get(client_handle)?.clone();
+            let value = ItemValue {
+                value: value.into(),
+                quality: *quality,
+                timestamp: *timestamp,
+            };
+            Some((*client_handle, (name, value)))
+        })
+        .collect()
+}
+
+impl tokio_stream::Stream for ItemUpdates {
+    type Item = (String, ItemValue);
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        loop {
+            if let Some(update) = self.pending.pop_front() {
+                return std::task::Poll::Ready(Some(update));
+            }
+
+            match std::pin::Pin::new(&mut self.events).poll_next(cx) {
+                std::task::Poll::Ready(Some(Ok(event))) => self.push(event),
+                std::task::Poll::Ready(Some(Err(
+                    tokio_stream::wrappers::errors::BroadcastStreamRecvError::Lagged(skipped),
+                ))) => {
+                    log::warn!("Item subscription lagged, {skipped} data change events dropped");
+                    self.lagged += skipped;
+                }
+                std::task::Poll::Ready(None) => return std::task::Poll::Ready(None),
+                std::task::Poll::Pending => return std::task::Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Stream of coalesced `(name, value)` updates returned by [`Group::debounced_stream`].
+pub struct DebouncedUpdates {
+    names: HashMap<u32, String>,
+    events: tokio_stream::wrappers::BroadcastStream<OwnedDataChangeEvent>,
+    window: std::time::Duration,
+    /// Created on the first poll, as timers need a running runtime.
+    interval: Option<tokio::time::Interval>,
+    /// Latest update per client handle, in the order items first changed.
+    latest: HashMap<u32, (String, ItemValue)>,
+    order: Vec<u32>,
+    pending: std::collections::VecDeque<(String, ItemValue)>,
+    closed: bool,
+    lagged: u64,
+}
+
+impl DebouncedUpdates {
+    /// Returns the number of data change events dropped because the
+    /// subscriber fell behind, since subscribing.
+    pub fn lagged(&self) -> u64 {
+        self.lagged
+    }
+
+    fn flush(&mut self) {
+        for client_handle in self.order.drain(..) {
+            if let Some(update) = self.latest.remove(&client_handle) {
+                self.pending.push_back(update);
+            }
+        }
+    }
+}
+
+impl tokio_stream::Stream for DebouncedUpdates {
+    type Item = (String, ItemValue);
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(update) = this.pending.pop_front() {
+                return std::task::Poll::Ready(Some(update));
+            }
+
+            if this.closed {
+                return std::task::Poll::Ready(None);
+            }
+
+            while let std::task::Poll::Ready(event) =
+                std::pin::Pin::new(&mut this.events).poll_next(cx)
+            {
+                match event {
+                    Some(Ok(event)) => {
+                        for (client_handle, update) in decode_updates(&this.names, event) {
+                            if this.latest.insert(client_handle, update).is_none() {
+                                this.order.push(client_handle);
+                            }
+                        }
+                    }
+                    Some(Err(
+                        tokio_stream::wrappers::errors::BroadcastStreamRecvError::Lagged(skipped),
+                    )) => {
+                        log::warn!(
+                            "Debounced subscription lagged, {skipped} data change events dropped"
+                        );
+                        this.lagged += skipped;
+                    }
+                    None => {
+                        // The group is gone, deliver what is left without waiting.
+                        this.closed = true;
+                        this.flush();
+                        break;
+                    }
+                }
+            }
+
+            if this.closed {
+                continue;
+            }
+
+            let window = this.window;
+            let interval = this.interval.get_or_insert_with(|| {
+                let mut interval =
+                    tokio::time::interval_at(tokio::time::Instant::now() + window, window);
+                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                interval
+            });
+
+            // An empty flush loops back and waits for the next tick.
+            match interval.poll_tick(cx) {
+                std::task::Poll::Ready(_) => this.flush(),
+                std::task::Poll::Pending => return std::task::Poll::Pending,
+            }
+        }
     }
 }
 
@@ -648,10 +2360,7 @@ impl<T> std::future::Future for DataCallbackFuture<T> {
         match self.receiver.as_mut().poll(cx) {
             std::task::Poll::Ready(Ok(event)) => std::task::Poll::Ready(Ok(event)),
             std::task::Poll::Ready(Err(_)) => {
-                std::task::Poll::Ready(Err(windows_core::Error::new(
-                    windows::Win32::Foundation::E_FAIL,
-                    "data change event receiver dropped",
-                )))
+                std::task::Poll::Ready(Err(error::fail("data change event receiver dropped")))
             }
             std::task::Poll::Pending => std::task::Poll::Pending,
         }
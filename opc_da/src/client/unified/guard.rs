@@ -1,3 +1,5 @@
+use windows::Win32::System::Com::IGlobalInterfaceTable;
+
 /// A RAII guard that manages COM initialization and uninitialization for a thread.
 ///
 /// This type ensures that COM is properly initialized when the guard is created and
@@ -35,6 +37,41 @@ impl<T> Guard<T> {
 
         Ok(guard)
     }
+
+    /// Creates a new guard that initializes a single-threaded apartment.
+    ///
+    /// [`Guard::new`] joins the multithreaded apartment, where message
+    /// filters such as [`Client::set_call_timeout`](super::Client::set_call_timeout)
+    /// are not supported. In a single-threaded apartment callbacks are
+    /// delivered through the window message queue, so the thread must pump
+    /// messages while waiting for them.
+    ///
+    /// Wrappers created on the thread stay bound to it. Operations that run
+    /// on a worker thread, such as
+    /// [`Group::read_items_sync_timeout`](super::Group::read_items_sync_timeout)
+    /// and [`Group::poll_changes`](super::Group::poll_changes), never use the
+    /// wrapper's pointers there: the worker joins the multithreaded apartment
+    /// and unmarshals its own proxy through the global interface table.
+    ///
+    /// # Errors
+    /// Returns `RPC_E_CHANGED_MODE` if the thread already joined the
+    /// multithreaded apartment.
+    pub fn new_single_threaded(value: T) -> windows::core::Result<Self> {
+        let guard = Self {
+            inner: value,
+            _marker: std::marker::PhantomData,
+        };
+
+        unsafe {
+            windows::Win32::System::Com::CoInitializeEx(
+                None,
+                windows::Win32::System::Com::COINIT_APARTMENTTHREADED,
+            )
+        }
+        .ok()?;
+
+        Ok(guard)
+    }
 }
 
 /// Provides direct access to the wrapped value through reference.
@@ -81,6 +118,7 @@ impl<T> Guard<T> {
     ///
     /// # Thread Safety
     /// COM initialization is performed with COINIT_MULTITHREADED flag.
+    #[cfg_attr(not(feature = "async"), allow(dead_code))]
     pub(crate) fn initialize() {
         Self::try_initialize().expect("Failed to initialize COM");
     }
@@ -94,3 +132,123 @@ impl<T> Guard<T> {
         unsafe { windows::Win32::System::Com::CoUninitialize() };
     }
 }
+
+/// Records the thread a COM wrapper was created on.
+///
+/// In debug builds [`ThreadAffinity::check`] panics when called from any other
+/// thread, which turns a cross-thread COM call into an immediate, descriptive
+/// failure instead of an intermittent `RPC_E_WRONG_THREAD`. In release builds
+/// the type is zero-sized and the check compiles to nothing.
+///
+/// The rule is stricter than COM's, which would allow any thread of the
+/// multithreaded apartment, so that the same code also works in a
+/// single-threaded one. The crate follows it internally: a worker thread
+/// never touches the pointers of a checked wrapper and gets its own proxy
+/// through [`GlobalInterface`] instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ThreadAffinity {
+    #[cfg(debug_assertions)]
+    owner: std::thread::ThreadId,
+}
+
+impl ThreadAffinity {
+    /// Binds to the current thread.
+    pub(crate) fn current() -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            owner: std::thread::current().id(),
+        }
+    }
+
+    /// Asserts that the current thread is the one the value was created on.
+    ///
+    /// # Panics
+    /// Panics in debug builds if called from a different thread.
+    #[track_caller]
+    #[inline]
+    #[cfg_attr(not(feature = "async"), allow(dead_code))]
+    pub(crate) fn check(&self, type_name: &str) {
+        #[cfg(debug_assertions)]
+        {
+            let current = std::thread::current();
+            assert!(
+                current.id() == self.owner,
+                "{type_name} was created on thread {:?} but used on thread {:?} ({}); \
+                 COM interface pointers must be used on the thread that created them",
+                self.owner,
+                current.id(),
+                current.name().unwrap_or("<unnamed>"),
+            );
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = type_name;
+    }
+}
+
+/// An interface registered in the COM global interface table.
+///
+/// Interface pointers belong to the apartment that created them, so a worker
+/// thread never uses the pointer of a wrapper. It moves the registration
+/// cookie instead and calls [`GlobalInterface::get`] to unmarshal a proxy for
+/// its own apartment. This works whether the creating thread joined the
+/// multithreaded apartment or a single-threaded one.
+///
+/// The registration is revoked when the value is dropped, which must happen
+/// while COM is still initialized on the dropping thread.
+#[derive(Debug)]
+#[cfg_attr(not(feature = "async"), allow(dead_code))]
+pub(crate) struct GlobalInterface<I> {
+    cookie: u32,
+    _interface: std::marker::PhantomData<fn() -> I>,
+}
+
+#[cfg_attr(not(feature = "async"), allow(dead_code))]
+impl<I: windows::core::Interface> GlobalInterface<I> {
+    /// Registers `interface`, which must belong to the current apartment.
+    pub(crate) fn register(interface: &I) -> windows::core::Result<Self> {
+        let cookie = unsafe {
+            global_interface_table()?
+                .RegisterInterfaceInGlobal(&interface.cast::<windows::core::IUnknown>()?, &I::IID)?
+        };
+
+        Ok(Self {
+            cookie,
+            _interface: std::marker::PhantomData,
+        })
+    }
+
+    /// Returns a pointer to the interface usable in the current apartment.
+    pub(crate) fn get(&self) -> windows::core::Result<I> {
+        let mut pointer = core::ptr::null_mut();
+        unsafe {
+            global_interface_table()?.GetInterfaceFromGlobal(self.cookie, &I::IID, &mut pointer)?;
+            Ok(I::from_raw(pointer))
+        }
+    }
+}
+
+impl<I> Drop for GlobalInterface<I> {
+    fn drop(&mut self) {
+        let result = global_interface_table()
+            .and_then(|table| unsafe { table.RevokeInterfaceFromGlobal(self.cookie) });
+        if let Err(error) = result {
+            log::warn!("Failed to revoke global interface {}: {error}", self.cookie);
+        }
+    }
+}
+
+/// `CLSID_StdGlobalInterfaceTable`, the process-wide global interface table.
+#[cfg_attr(not(feature = "async"), allow(dead_code))]
+const STD_GLOBAL_INTERFACE_TABLE: windows::core::GUID =
+    windows::core::GUID::from_u128(0x00000323_0000_0000_c000_000000000046);
+
+#[cfg_attr(not(feature = "async"), allow(dead_code))]
+fn global_interface_table() -> windows::core::Result<IGlobalInterfaceTable> {
+    unsafe {
+        windows::Win32::System::Com::CoCreateInstance(
+            &STD_GLOBAL_INTERFACE_TABLE,
+            None,
+            windows::Win32::System::Com::CLSCTX_INPROC_SERVER,
+        )
+    }
+}
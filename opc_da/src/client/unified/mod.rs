@@ -1,14 +1,28 @@
+#[cfg(feature = "async")]
 pub mod actor;
+#[cfg(feature = "async")]
+pub mod blocking;
+#[cfg(feature = "async")]
 pub mod client;
+pub mod filter;
+#[cfg(feature = "async")]
 pub mod group;
 pub mod guard;
+#[cfg(feature = "async")]
 pub mod server;
 
+#[cfg(feature = "async")]
 pub use actor::*;
+#[cfg(feature = "async")]
+pub use blocking::*;
+#[cfg(feature = "async")]
 pub use client::*;
+pub use filter::*;
+#[cfg(feature = "async")]
 pub use group::*;
 pub use guard::*;
+#[cfg(feature = "async")]
 pub use server::*;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "async"))]
 mod tests;
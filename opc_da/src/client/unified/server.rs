@@ -1,10 +1,27 @@
+use opc_classic_utils::{CalleeAllocatedArray, CalleeAllocatedPtrArray};
+use windows::core::Interface as _;
+
 use crate::{
-    client::{v1, v2, v3, ServerTrait},
-    def::{BrowseFilter, BrowseType, EnumScope, GroupState, ServerStatus},
-    utils::{ToNative as _, TryToLocal},
+    client::{
+        BrowseTrait as _, CommonTrait, ItemPropertiesTrait, ServerPublicGroupsTrait, ServerTrait,
+        v1, v2, v3,
+    },
+    def::{
+        AvailableProperty, BrowseElement, BrowseFilter, BrowseType, EnumScope, EuInfo, EuType,
+        GroupState, ItemResult, PropertyId, ServerState, ServerStatus, SubscriptionSpec, Variant,
+        Version, Vqt,
+    },
+    error::{self, OpcError},
+    utils::{
+        LocalPointer, RemoteArray, RemotePointer, ToNative as _, TryFromNative as _, TryToLocal,
+        resolve_clsid, supports_interface,
+    },
 };
 
-use super::Group;
+use super::{Group, GroupBuilder, GroupInner};
+
+/// `LOCALE_SYSTEM_DEFAULT`, defined here to avoid the `Win32_Globalization` feature.
+const LOCALE_SYSTEM_DEFAULT: u32 = 0x0800;
 
 pub enum Server {
     V1(v1::Server),
@@ -13,6 +30,109 @@ pub enum Server {
 }
 
 impl Server {
+    /// Asserts in debug builds that the server is used on the thread that created it.
+    #[track_caller]
+    fn check_thread(&self) {
+        match self {
+            Self::V1(server) => server.affinity.check("Server"),
+            Self::V2(server) => server.affinity.check("Server"),
+            Self::V3(server) => server.affinity.check("Server"),
+        }
+    }
+
+    /// Connects to the server registered under `progid`.
+    ///
+    /// `progid` may also be a CLSID, see [`resolve_clsid`].
+    ///
+    /// The highest OPC DA version whose interfaces the server implements is
+    /// selected, probing 3.0 (`IOPCBrowse`), then 2.0 (`IOPCCommon`), then 1.0.
+    ///
+    /// # Errors
+    /// Returns an error if the ProgID is not registered, the server cannot be
+    /// created, or it does not implement `IOPCServer`.
+    pub fn connect(progid: &str) -> windows::core::Result<Self> {
+        let unknown = Self::create_instance(progid)?;
+
+        v3::Server::try_from(unknown.clone())
+            .map(Self::V3)
+            .or_else(|_| v2::Server::try_from(unknown.clone()).map(Self::V2))
+            .or_else(|_| v1::Server::try_from(unknown).map(Self::V1))
+            .map_err(|e| {
+                windows::core::Error::new(
+                    e.code(),
+                    format!("{progid} is not an OPC DA server: {}", e.message()),
+                )
+            })
+    }
+
+    /// Connects to the server registered under `progid` as a specific version.
+    ///
+    /// # Errors
+    /// Returns `E_NOINTERFACE` if the server does not implement the interfaces
+    /// required by `version`.
+    pub fn connect_version(progid: &str, version: Version) -> windows::core::Result<Self> {
+        let unknown = Self::create_instance(progid)?;
+
+        match version {
+            Version::V1 => v1::Server::try_from(unknown).map(Self::V1),
+            Version::V2 => v2::Server::try_from(unknown).map(Self::V2),
+            Version::V3 => v3::Server::try_from(unknown).map(Self::V3),
+        }
+        .map_err(|e| {
+            windows::core::Error::new(
+                windows::Win32::Foundation::E_NOINTERFACE,
+                format!("{progid} does not support {version:?}: {}", e.message()),
+            )
+        })
+    }
+
+    fn create_instance(progid: &str) -> windows::core::Result<windows::core::IUnknown> {
+        let class_id = resolve_clsid(progid)?;
+
+        unsafe {
+            windows::Win32::System::Com::CoCreateInstance(
+                &class_id,
+                None,
+                windows::Win32::System::Com::CLSCTX_ALL,
+            )
+        }
+        .map_err(|e| {
+            windows::core::Error::new(
+                e.code(),
+                format!("Failed to create {progid}: {}", e.message()),
+            )
+        })
+    }
+
+    /// Reports which OPC interfaces the server object implements.
+    ///
+    /// Every interface is queried on the server object, independent of the
+    /// version wrapper in use. Probing releases each queried reference
+    /// immediately, see [`supports_interface`](crate::utils::supports_interface).
+    pub fn supported_interfaces(&self) -> SupportedInterfaces {
+        self.check_thread();
+        let server = match self {
+            Self::V1(server) => &server.server,
+            Self::V2(server) => &server.server,
+            Self::V3(server) => &server.server,
+        };
+
+        let server: &windows::core::IUnknown = server.into();
+        let supports = |interface_id| supports_interface(server, &interface_id);
+
+        SupportedInterfaces {
+            common: supports(opc_comn_bindings::IOPCCommon::IID),
+            connection_point_container: supports(
+                windows::Win32::System::Com::IConnectionPointContainer::IID,
+            ),
+            item_properties: supports(opc_da_bindings::IOPCItemProperties::IID),
+            public_groups: supports(opc_da_bindings::IOPCServerPublicGroups::IID),
+            browse_address_space: supports(opc_da_bindings::IOPCBrowseServerAddressSpace::IID),
+            browse: supports(opc_da_bindings::IOPCBrowse::IID),
+            item_io: supports(opc_da_bindings::IOPCItemIO::IID),
+        }
+    }
+
     fn add_group_with_server<
         G: TryFrom<windows::core::IUnknown, Error = windows::core::Error>,
         T: ServerTrait<G>,
@@ -33,15 +153,225 @@ impl Server {
         )
     }
 
-    pub fn add_group(&self, state: GroupState) -> windows::core::Result<Group> {
+    /// Adds a group and advises its data callback.
+    ///
+    /// The group is boxed because the advised callback refers to it, so it
+    /// must not move until it is dropped.
+    pub fn add_group(&self, builder: impl Into<GroupBuilder>) -> windows::core::Result<Box<Group>> {
+        self.check_thread();
+        let GroupBuilder {
+            state,
+            value_cache_ttl,
+            broadcast_capacity,
+            add_batch_size,
+            coerce_write_types,
+            allow_null_writes,
+        } = builder.into();
+
+        let inner = match self {
+            Self::V1(server) => GroupInner::V1(Self::add_group_with_server(server, state)?),
+            Self::V2(server) => GroupInner::V2(Self::add_group_with_server(server, state)?),
+            Self::V3(server) => GroupInner::V3(Self::add_group_with_server(server, state)?),
+        };
+
+        let mut group = Box::new(Group::with_broadcast_capacity(
+            inner,
+            value_cache_ttl,
+            broadcast_capacity.unwrap_or(Group::DEFAULT_BROADCAST_CAPACITY),
+        ));
+        if let Some(size) = add_batch_size {
+            group.set_add_batch_size(size);
+        }
+        group.set_coerce_write_types(coerce_write_types);
+        group.set_allow_null_writes(allow_null_writes);
+        group.initialize()?;
+
+        Ok(group)
+    }
+
+    /// Recreates a group captured with [`Group::to_spec`].
+    ///
+    /// # Returns
+    /// The group and the results of adding the spec items, in spec order.
+    #[allow(clippy::type_complexity)]
+    pub fn create_group_from_spec(
+        &self,
+        spec: SubscriptionSpec,
+    ) -> windows::core::Result<(Box<Group>, Vec<windows::core::Result<ItemResult>>)> {
+        let SubscriptionSpec { state, items } = spec;
+        let group = self.add_group(GroupState {
+            server_handle: 0,
+            ..state
+        })?;
+        let results = if items.is_empty() {
+            vec![]
+        } else {
+            group.add(items)?
+        };
+
+        Ok((group, results))
+    }
+
+    /// Gets a group created earlier by its name.
+    ///
+    /// The returned group shares the server side group with the original one
+    /// but is not initialized, as servers usually accept a single data
+    /// callback per group.
+    ///
+    /// # Errors
+    /// Returns E_INVALIDARG if no group with that name exists
+    pub fn group_by_name(&self, name: &str) -> windows::core::Result<Group> {
+        self.check_thread();
+        if name.is_empty() {
+            return Err(error::invalid_arg("group name is empty"));
+        }
+
         match self {
-            Self::V1(server) => Ok(Self::add_group_with_server(server, state)?.into()),
-            Self::V2(server) => Ok(Self::add_group_with_server(server, state)?.into()),
-            Self::V3(server) => Ok(Self::add_group_with_server(server, state)?.into()),
+            Self::V1(server) => Ok(server.get_group_by_name(name)?.into()),
+            Self::V2(server) => Ok(server.get_group_by_name(name)?.into()),
+            Self::V3(server) => Ok(server.get_group_by_name(name)?.into()),
         }
     }
 
+    /// Attaches to a public group by its name.
+    ///
+    /// The group is not initialized, see [`group_by_name`](Self::group_by_name).
+    ///
+    /// # Errors
+    /// Returns E_NOTIMPL for V3 servers and servers without
+    /// `IOPCServerPublicGroups`
+    pub fn public_group_by_name(&self, name: &str) -> windows::core::Result<Group> {
+        self.check_thread();
+        let iid = opc_da_bindings::IOPCItemMgt::IID;
+
+        match self {
+            Self::V1(server) => {
+                Ok(v1::Group::try_from(server.get_public_group_by_name(name, &iid)?)?.into())
+            }
+            Self::V2(server) => {
+                Ok(v2::Group::try_from(server.get_public_group_by_name(name, &iid)?)?.into())
+            }
+            Self::V3(_) => Err(error::not_impl(
+                "IOPCServerPublicGroups not supported for v3",
+            )),
+        }
+    }
+
+    /// Checks that the server answers, as a cheap keep-alive.
+    ///
+    /// OPC DA has no dedicated ping, so this calls `GetStatus` and keeps only
+    /// the state. An error means the connection is broken; a state other than
+    /// [`ServerState::Running`] means the server answers but does not serve
+    /// data, e.g. while it is suspended.
+    pub fn ping(&self) -> windows::core::Result<ServerState> {
+        self.get_status().map(|status| status.server_state)
+    }
+
+    /// Gets the vendor specific information from the server status.
+    pub fn vendor_info(&self) -> windows::core::Result<String> {
+        self.get_status().map(|status| status.vendor_info)
+    }
+
+    /// Gets the server software version from the server status.
+    ///
+    /// # Returns
+    /// The major version, minor version and build number
+    pub fn version(&self) -> windows::core::Result<(u16, u16, u16)> {
+        self.get_status().map(|status| {
+            (
+                status.major_version,
+                status.minor_version,
+                status.build_number,
+            )
+        })
+    }
+
+    /// Lists the locale IDs the server supports.
+    ///
+    /// # Errors
+    /// Returns E_NOTIMPL for V1 servers, which lack `IOPCCommon`.
+    pub fn available_locale_ids(&self) -> windows::core::Result<Vec<u32>> {
+        self.check_thread();
+        let locale_ids = match self {
+            Self::V1(_) => return Err(error::not_impl("IOPCCommon not supported for v1")),
+            Self::V2(server) => server.query_available_locale_ids()?,
+            Self::V3(server) => server.query_available_locale_ids()?,
+        };
+
+        Ok(locale_ids.as_slice().to_vec())
+    }
+
+    /// Gets the locale the server uses for this client.
+    ///
+    /// # Errors
+    /// Returns E_NOTIMPL for V1 servers, which lack `IOPCCommon`.
+    pub fn locale_id(&self) -> windows::core::Result<u32> {
+        self.check_thread();
+        match self {
+            Self::V1(_) => Err(error::not_impl("IOPCCommon not supported for v1")),
+            Self::V2(server) => server.get_locale_id(),
+            Self::V3(server) => server.get_locale_id(),
+        }
+    }
+
+    /// Sets the locale the server uses for this client, e.g. for error strings.
+    ///
+    /// # Errors
+    /// Returns E_NOTIMPL for V1 servers, which lack `IOPCCommon`.
+    pub fn set_locale_id(&self, locale_id: u32) -> windows::core::Result<()> {
+        self.check_thread();
+        match self {
+            Self::V1(_) => Err(error::not_impl("IOPCCommon not supported for v1")),
+            Self::V2(server) => server.set_locale_id(locale_id),
+            Self::V3(server) => server.set_locale_id(locale_id),
+        }
+    }
+
+    /// Sets the first locale of `preferred` that the server supports.
+    ///
+    /// The current locale is kept if the server supports none of them.
+    ///
+    /// # Returns
+    /// The locale in use afterwards
+    ///
+    /// # Errors
+    /// Returns E_NOTIMPL for V1 servers, which lack `IOPCCommon`.
+    pub fn set_best_locale(&self, preferred: &[u32]) -> windows::core::Result<u32> {
+        let available = self.available_locale_ids()?;
+        match preferred
+            .iter()
+            .find(|locale_id| available.contains(locale_id))
+        {
+            Some(&locale_id) => {
+                self.set_locale_id(locale_id)?;
+                Ok(locale_id)
+            }
+            None => self.locale_id(),
+        }
+    }
+
+    /// Gets the server's description of an error code.
+    ///
+    /// OPC DA 1.0 servers are asked for the description in the system default locale.
+    pub fn error_string(&self, error: windows::core::HRESULT) -> windows::core::Result<String> {
+        self.check_thread();
+        match self {
+            Self::V1(server) => ServerTrait::get_error_string(server, error, LOCALE_SYSTEM_DEFAULT),
+            Self::V2(server) => CommonTrait::get_error_string(server, error),
+            Self::V3(server) => CommonTrait::get_error_string(server, error),
+        }
+    }
+
+    /// Wraps `error` with the server's description of its code.
+    ///
+    /// Falls back to the system message if the server does not know the code.
+    pub fn describe_error(&self, error: windows::core::Error) -> OpcError {
+        let message = self.error_string(error.code()).unwrap_or_default();
+        OpcError::new(error, message)
+    }
+
     pub fn get_status(&self) -> windows::core::Result<ServerStatus> {
+        self.check_thread();
         let status = match self {
             Self::V1(server) => server.get_status(),
             Self::V2(server) => server.get_status(),
@@ -52,6 +382,7 @@ impl Server {
     }
 
     pub fn remove_group(&self, server_handle: u32, force: bool) -> windows::core::Result<()> {
+        self.check_thread();
         match self {
             Self::V1(server) => server.remove_group(server_handle, force),
             Self::V2(server) => server.remove_group(server_handle, force),
@@ -63,6 +394,7 @@ impl Server {
         &self,
         scope: EnumScope,
     ) -> windows::core::Result<GroupIterator> {
+        self.check_thread();
         let scope = scope.to_native();
 
         let iterator = match self {
@@ -73,6 +405,569 @@ impl Server {
 
         Ok(iterator)
     }
+
+    /// Lists the names of the groups in `scope`.
+    ///
+    /// `EnumScope::Public` lists the groups shared by the server,
+    /// `EnumScope::Private` the ones created by this client.
+    pub fn group_names(&self, scope: EnumScope) -> windows::core::Result<Vec<String>> {
+        self.check_thread();
+        let scope = scope.to_native();
+
+        match self {
+            Self::V1(server) => server.create_group_name_enumerator(scope)?.collect(),
+            Self::V2(server) => server.create_group_name_enumerator(scope)?.collect(),
+            Self::V3(server) => server.create_group_name_enumerator(scope)?.collect(),
+        }
+    }
+
+    /// Lists the groups in `scope`.
+    ///
+    /// The groups are not initialized, see [`group_by_name`](Self::group_by_name).
+    pub fn groups(&self, scope: EnumScope) -> windows::core::Result<Vec<Group>> {
+        self.create_group_enumerator(scope)?.collect()
+    }
+
+    /// Lists the private and public groups.
+    pub fn groups_all(&self) -> windows::core::Result<Vec<Group>> {
+        self.groups(EnumScope::All)
+    }
+
+    /// Reads several properties of one item.
+    ///
+    /// # Arguments
+    /// * `item_id` - Fully qualified item ID
+    /// * `property_ids` - Property IDs to read
+    ///
+    /// # Returns
+    /// Each property ID paired with its decoded value or per-property error
+    ///
+    /// # Errors
+    /// Returns E_NOTIMPL if the server does not support `IOPCItemProperties`
+    pub fn item_properties(
+        &self,
+        item_id: &str,
+        property_ids: &[PropertyId],
+    ) -> windows::core::Result<Vec<(PropertyId, windows::core::Result<Variant>)>> {
+        self.check_thread();
+        let raw_ids = property_ids
+            .iter()
+            .map(|&property_id| property_id.into())
+            .collect::<Vec<u32>>();
+        let (values, errors) = match self {
+            Self::V2(server) => server.get_item_properties(item_id, &raw_ids)?,
+            Self::V3(server) => server.get_item_properties(item_id, &raw_ids)?,
+            Self::V1(_) => {
+                return Err(error::not_impl("IOPCItemProperties not supported"));
+            }
+        };
+
+        let results = property_ids
+            .iter()
+            .zip(values.into_variants())
+            .zip(errors.as_slice())
+            .map(|((property_id, value), error)| {
+                if error.is_ok() {
+                    (*property_id, Ok(value))
+                } else {
+                    (*property_id, Err((*error).into()))
+                }
+            })
+            .collect();
+
+        Ok(results)
+    }
+
+    /// Reads the canonical data type (VT_*) of an item from property 1.
+    ///
+    /// Lighter than validating the item in a group, and some servers expose
+    /// the property even when validation is restricted.
+    ///
+    /// # Errors
+    /// Returns the per-property error if the server does not provide the
+    /// property, E_NOTIMPL if it does not support `IOPCItemProperties` and
+    /// E_FAIL if the value is not a VT type.
+    pub fn item_data_type(&self, item_id: &str) -> windows::core::Result<u16> {
+        let value = self
+            .item_properties(item_id, &[PropertyId::DataType])?
+            .pop()
+            .map(|(_, value)| value)
+            .unwrap_or_else(|| Err(error::fail("Property value not returned")))?;
+
+        match value {
+            Variant::I16(data_type) => Some(data_type as u16),
+            Variant::U16(data_type) => Some(data_type),
+            Variant::I32(data_type) => u16::try_from(data_type).ok(),
+            Variant::U32(data_type) => u16::try_from(data_type).ok(),
+            _ => None,
+        }
+        .ok_or_else(|| error::fail(format!("Invalid data type property of {item_id}")))
+    }
+
+    /// Reads the value, quality and timestamp properties (2, 3 and 4) of an item.
+    ///
+    /// Useful to compare them with a direct read, see [`Vqt::from_properties`]
+    /// for the accepted encodings.
+    ///
+    /// # Errors
+    /// Returns the first per-property error and E_NOTIMPL if the server does
+    /// not support `IOPCItemProperties`.
+    pub fn item_vqt_properties(&self, item_id: &str) -> windows::core::Result<Vqt> {
+        self.check_thread();
+        let property_ids = [
+            PropertyId::Value,
+            PropertyId::Quality,
+            PropertyId::Timestamp,
+        ]
+        .map(u32::from);
+        let (mut values, errors) = match self {
+            Self::V2(server) => server.get_item_properties(item_id, &property_ids)?,
+            Self::V3(server) => server.get_item_properties(item_id, &property_ids)?,
+            Self::V1(_) => {
+                return Err(error::not_impl("IOPCItemProperties not supported"));
+            }
+        };
+
+        let vqt = match (values.as_slice(), errors.as_slice()) {
+            ([value, quality, timestamp], [value_error, quality_error, timestamp_error]) => {
+                value_error
+                    .ok()
+                    .and(quality_error.ok())
+                    .and(timestamp_error.ok())
+                    .and_then(|_| Vqt::from_properties(value, quality, timestamp))
+            }
+            _ => Err(error::fail("Property values not returned")),
+        };
+        values.clear_variants();
+
+        vqt
+    }
+
+    /// Reads the engineering units of an item from properties 7, 8, 100, 102 and 103.
+    ///
+    /// Analog ranges come from the EU info (property 8), or from the high
+    /// and low limits (102 and 103) when the server does not provide it.
+    /// Enumerated items return their state labels.
+    ///
+    /// # Returns
+    /// `None` if the item has no engineering units
+    ///
+    /// # Errors
+    /// Returns E_NOTIMPL if the server does not support `IOPCItemProperties`,
+    /// and `DISP_E_TYPEMISMATCH` if an analog item has no decodable range.
+    pub fn engineering_units(&self, item_id: &str) -> windows::core::Result<Option<EuInfo>> {
+        self.check_thread();
+        let property_ids = [
+            PropertyId::EuType,
+            PropertyId::EuInfo,
+            PropertyId::EuUnits,
+            PropertyId::EuHighLimit,
+            PropertyId::EuLowLimit,
+        ]
+        .map(u32::from);
+        let (mut values, errors) = match self {
+            Self::V2(server) => server.get_item_properties(item_id, &property_ids)?,
+            Self::V3(server) => server.get_item_properties(item_id, &property_ids)?,
+            Self::V1(_) => {
+                return Err(error::not_impl("IOPCItemProperties not supported"));
+            }
+        };
+
+        let eu_info = match (values.as_slice(), errors.as_slice()) {
+            (values @ [_, _, _, _, _], errors @ [_, _, _, _, _]) => {
+                Self::decode_engineering_units(values, errors)
+            }
+            _ => Err(error::fail("Property values not returned")),
+        };
+        values.clear_variants();
+
+        eu_info
+    }
+
+    /// Decodes the properties read by [`engineering_units`](Self::engineering_units).
+    fn decode_engineering_units(
+        values: &[windows::Win32::System::Variant::VARIANT],
+        errors: &[windows::core::HRESULT],
+    ) -> windows::core::Result<Option<EuInfo>> {
+        let property = |index: usize| errors[index].is_ok().then(|| Variant::from(&values[index]));
+        let number = |index: usize| match property(index)?
+            .coerce_to(windows::Win32::System::Variant::VT_R8.0)
+        {
+            Ok(Variant::F64(value)) => Some(value),
+            _ => None,
+        };
+
+        let eu_type = match property(0) {
+            Some(Variant::I32(eu_type)) => Some(EuType::try_from_native(
+                &opc_da_bindings::tagOPCEUTYPE(eu_type),
+            )?),
+            Some(Variant::I16(eu_type)) => Some(EuType::try_from_native(
+                &opc_da_bindings::tagOPCEUTYPE(eu_type.into()),
+            )?),
+            _ => None,
+        };
+
+        let eu_info = match eu_type {
+            Some(EuType::NoEnum) => return Ok(None),
+            Some(eu_type) if errors[1].is_ok() => match EuInfo::from_native(&eu_type, &values[1]) {
+                Ok(eu_info) => eu_info,
+                // Fall back to the limit properties below.
+                Err(_) if eu_type == EuType::Analog => None,
+                Err(error) => return Err(error),
+            },
+            _ => None,
+        };
+
+        let eu_info = match (eu_info, number(4), number(3)) {
+            (Some(eu_info), _, _) => eu_info,
+            (None, Some(low), Some(high)) if eu_type != Some(EuType::Enumerated) => {
+                EuInfo::Analog {
+                    low,
+                    high,
+                    units: None,
+                }
+            }
+            (None, _, _) if eu_type.is_none() => return Ok(None),
+            (None, _, _) => {
+                return Err(windows::core::Error::new(
+                    windows::Win32::Foundation::DISP_E_TYPEMISMATCH,
+                    "EU info is missing or not decodable",
+                ));
+            }
+        };
+
+        Ok(Some(match eu_info {
+            EuInfo::Analog { low, high, .. } => EuInfo::Analog {
+                low,
+                high,
+                units: match property(2) {
+                    Some(Variant::String(units)) if !units.is_empty() => Some(units),
+                    _ => None,
+                },
+            },
+            enumerated => enumerated,
+        }))
+    }
+
+    /// Queries the properties available for an item.
+    ///
+    /// # Arguments
+    /// * `item_id` - Fully qualified item ID
+    ///
+    /// # Returns
+    /// The ID, description and data type (VT_*) of every available property
+    ///
+    /// # Errors
+    /// Returns E_NOTIMPL if the server does not support `IOPCItemProperties`
+    pub fn available_properties(
+        &self,
+        item_id: &str,
+    ) -> windows::core::Result<Vec<AvailableProperty>> {
+        self.check_thread();
+        let interface = match self {
+            Self::V2(server) => ItemPropertiesTrait::interface(server)?,
+            Self::V3(server) => ItemPropertiesTrait::interface(server)?,
+            Self::V1(_) => {
+                return Err(error::not_impl("IOPCItemProperties not supported"));
+            }
+        };
+
+        let item_id = LocalPointer::from(item_id);
+
+        let mut count = 0;
+        let mut property_ids = core::ptr::null_mut();
+        let mut descriptions = core::ptr::null_mut();
+        let mut data_types = core::ptr::null_mut();
+
+        unsafe {
+            interface.QueryAvailableProperties(
+                item_id.as_pcwstr(),
+                &mut count,
+                &mut property_ids,
+                &mut descriptions,
+                &mut data_types,
+            )?;
+        }
+
+        let count = count as usize;
+        let property_ids = CalleeAllocatedArray::from_raw(property_ids, count);
+        // PWSTR is a transparent wrapper, each string and the container are freed on drop.
+        let descriptions =
+            CalleeAllocatedPtrArray::from_raw(descriptions.cast::<*mut u16>(), count);
+        let data_types = CalleeAllocatedArray::from_raw(data_types, count);
+
+        let (Some(property_ids), Some(descriptions), Some(data_types)) = (unsafe {
+            (
+                property_ids.as_slice(),
+                descriptions.as_slice(),
+                data_types.as_slice(),
+            )
+        }) else {
+            return Ok(Vec::new());
+        };
+
+        property_ids
+            .iter()
+            .zip(descriptions)
+            .zip(data_types)
+            .map(|((property_id, description), data_type)| {
+                let description = if description.is_null() {
+                    String::new()
+                } else {
+                    unsafe { windows::core::PWSTR(*description).to_string() }?
+                };
+
+                Ok(AvailableProperty {
+                    property_id: *property_id,
+                    description,
+                    data_type: *data_type,
+                })
+            })
+            .collect()
+    }
+
+    /// Queries the available properties of an item and reads all of them.
+    ///
+    /// Combines `QueryAvailableProperties` and `GetItemProperties`, e.g. to
+    /// fetch the description (101) and engineering unit ranges (102, 103)
+    /// of an item together.
+    ///
+    /// # Arguments
+    /// * `item_id` - Fully qualified item ID
+    ///
+    /// # Returns
+    /// Every available property paired with its decoded value or
+    /// per-property error
+    ///
+    /// # Errors
+    /// Returns E_NOTIMPL if the server does not support `IOPCItemProperties`
+    pub fn all_item_properties(
+        &self,
+        item_id: &str,
+    ) -> windows::core::Result<Vec<(AvailableProperty, windows::core::Result<Variant>)>> {
+        let available = self.available_properties(item_id)?;
+        if available.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let property_ids = available
+            .iter()
+            .map(|property| PropertyId::from(property.property_id))
+            .collect::<Vec<_>>();
+        let mut values = self
+            .item_properties(item_id, &property_ids)?
+            .into_iter()
+            .map(|(property_id, value)| (u32::from(property_id), value))
+            .collect::<std::collections::BTreeMap<_, _>>();
+
+        Ok(available
+            .into_iter()
+            .map(|property| {
+                let value = values
+                    .remove(&property.property_id)
+                    .unwrap_or_else(|| Err(error::fail("Property value not returned")));
+                (property, value)
+            })
+            .collect())
+    }
+
+    /// Looks up the item IDs under which properties are readable as items.
+    ///
+    /// # Arguments
+    /// * `item_id` - Fully qualified item ID
+    /// * `property_ids` - Property IDs to look up
+    ///
+    /// # Returns
+    /// Each property ID paired with its item ID or per-property error
+    ///
+    /// # Errors
+    /// Returns E_INVALIDARG if property_ids is empty and E_NOTIMPL if the
+    /// server does not support `IOPCItemProperties`
+    pub fn lookup_item_ids(
+        &self,
+        item_id: &str,
+        property_ids: &[PropertyId],
+    ) -> windows::core::Result<Vec<(PropertyId, windows::core::Result<String>)>> {
+        self.check_thread();
+        let interface = match self {
+            Self::V2(server) => ItemPropertiesTrait::interface(server)?,
+            Self::V3(server) => ItemPropertiesTrait::interface(server)?,
+            Self::V1(_) => {
+                return Err(error::not_impl("IOPCItemProperties not supported"));
+            }
+        };
+
+        if property_ids.is_empty() {
+            return Err(error::invalid_arg("property_ids is empty"));
+        }
+
+        let raw_ids = property_ids
+            .iter()
+            .map(|&property_id| property_id.into())
+            .collect::<Vec<u32>>();
+        let item_id = LocalPointer::from(item_id);
+
+        let mut new_item_ids = core::ptr::null_mut();
+        let mut errors = core::ptr::null_mut();
+
+        unsafe {
+            interface.LookupItemIDs(
+                item_id.as_pcwstr(),
+                raw_ids.len().try_into()?,
+                raw_ids.as_ptr(),
+                &mut new_item_ids,
+                &mut errors,
+            )?;
+        }
+
+        let new_item_ids =
+            CalleeAllocatedPtrArray::from_raw(new_item_ids.cast::<*mut u16>(), property_ids.len());
+        let errors = CalleeAllocatedArray::from_raw(errors, property_ids.len());
+
+        let (Some(new_item_ids), Some(errors)) =
+            (unsafe { (new_item_ids.as_slice(), errors.as_slice()) })
+        else {
+            return Err(error::pointer("LookupItemIDs returned null arrays"));
+        };
+
+        Ok(property_ids
+            .iter()
+            .zip(new_item_ids)
+            .zip(errors)
+            .map(|((property_id, new_item_id), error)| {
+                let result = if error.is_err() {
+                    Err((*error).into())
+                } else if new_item_id.is_null() {
+                    Err(error::pointer("item ID is null"))
+                } else {
+                    unsafe { windows::core::PWSTR(*new_item_id).to_string() }.map_err(Into::into)
+                };
+
+                (*property_id, result)
+            })
+            .collect())
+    }
+
+    /// Browses the children of `item_id` one page at a time.
+    ///
+    /// The next page is requested only when the iterator is advanced after
+    /// the current one is consumed, so memory stays bounded by
+    /// [`BrowseElements::PAGE_SIZE`] however large the namespace is. Pass an
+    /// empty `item_id` to browse the root.
+    ///
+    /// Pages are fetched with a blocking COM call, so the iterator must be
+    /// advanced on the thread that created the server. From async code, keep
+    /// the server on a dedicated thread and send the elements to the
+    /// executor, for example over a channel.
+    ///
+    /// # Errors
+    /// The iterator yields E_NOTIMPL if the server does not support
+    /// `IOPCBrowse`, and ends after the first error.
+    pub fn browse_elements(&self, item_id: &str, filter: BrowseFilter) -> BrowseElements<'_> {
+        BrowseElements {
+            server: self,
+            item_id: item_id.to_string(),
+            filter,
+            continuation_point: None,
+            page: std::collections::VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+/// Iterator over the browse elements returned by [`Server::browse_elements`].
+pub struct BrowseElements<'a> {
+    server: &'a Server,
+    item_id: String,
+    filter: BrowseFilter,
+    continuation_point: Option<String>,
+    page: std::collections::VecDeque<BrowseElement>,
+    done: bool,
+}
+
+impl BrowseElements<'_> {
+    /// Maximum number of elements requested per page.
+    pub const PAGE_SIZE: u32 = 1000;
+
+    fn next_page(&mut self) -> windows::core::Result<()> {
+        self.server.check_thread();
+        let server = match self.server {
+            Server::V3(server) => server,
+            Server::V1(_) | Server::V2(_) => {
+                return Err(error::not_impl("IOPCBrowse not supported"));
+            }
+        };
+
+        // The continuation point is copied out of the server allocation, so
+        // it stays valid until it is passed back for the next page.
+        let (_, continuation_point, mut elements) = server.browse(
+            Some(&self.item_id),
+            self.continuation_point.take(),
+            Self::PAGE_SIZE,
+            self.filter.to_native(),
+            None::<&str>,
+            None::<&str>,
+            false,
+            false,
+            &[],
+        )?;
+
+        let page = elements
+            .as_slice()
+            .iter()
+            .map(BrowseElement::try_from_native)
+            .collect::<windows::core::Result<_>>();
+        elements
+            .as_mut_slice()
+            .iter_mut()
+            .for_each(free_browse_element);
+        self.page = page?;
+        self.continuation_point = continuation_point.filter(|point| !point.is_empty());
+        self.done = self.continuation_point.is_none();
+
+        Ok(())
+    }
+}
+
+impl Iterator for BrowseElements<'_> {
+    type Item = windows::core::Result<BrowseElement>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(element) = self.page.pop_front() {
+                return Some(Ok(element));
+            }
+
+            if self.done {
+                return None;
+            }
+
+            if let Err(error) = self.next_page() {
+                self.done = true;
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+/// Frees the strings and properties the server allocated for `element`.
+fn free_browse_element(element: &mut opc_da_bindings::tagOPCBROWSEELEMENT) {
+    drop(RemotePointer::from_raw(element.szName.0));
+    drop(RemotePointer::from_raw(element.szItemID.0));
+
+    let properties = &mut element.ItemProperties;
+    let mut properties = RemoteArray::from_mut_ptr(
+        core::mem::replace(&mut properties.pItemProperties, core::ptr::null_mut()),
+        core::mem::take(&mut properties.dwNumProperties),
+    );
+    for property in properties.as_mut_slice() {
+        drop(RemotePointer::from_raw(property.szItemID.0));
+        drop(RemotePointer::from_raw(property.szDescription.0));
+        drop(core::mem::take(&mut property.vValue));
+    }
+
+    element.szName = windows::core::PWSTR::null();
+    element.szItemID = windows::core::PWSTR::null();
 }
 
 impl From<v1::Server> for Server {
@@ -111,6 +1006,25 @@ impl Iterator for GroupIterator {
     }
 }
 
+/// Optional interfaces implemented by a server, see [`Server::supported_interfaces`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SupportedInterfaces {
+    /// `IOPCCommon`, required by OPC DA 2.0 and 3.0.
+    pub common: bool,
+    /// `IConnectionPointContainer` for shutdown notifications.
+    pub connection_point_container: bool,
+    /// `IOPCItemProperties`, OPC DA 2.0 and optional in 3.0.
+    pub item_properties: bool,
+    /// `IOPCServerPublicGroups`, optional in OPC DA 1.0 and 2.0.
+    pub public_groups: bool,
+    /// `IOPCBrowseServerAddressSpace`, optional in OPC DA 1.0 and 2.0.
+    pub browse_address_space: bool,
+    /// `IOPCBrowse`, OPC DA 3.0.
+    pub browse: bool,
+    /// `IOPCItemIO`, OPC DA 3.0.
+    pub item_io: bool,
+}
+
 pub struct BrowseItemsOptions {
     pub browse_type: BrowseType,
     pub browse_filter: BrowseFilter,
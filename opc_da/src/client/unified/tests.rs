@@ -18,3 +18,101 @@ fn test_unified() {
     let group_state = GroupState::default();
     let _ = server.add_group(group_state).expect("Failed to add group");
 }
+
+#[test]
+fn test_change_filters() {
+    let value = |value: f64, quality: u16| ItemValue {
+        value: value.into(),
+        quality,
+        timestamp: std::time::SystemTime::UNIX_EPOCH,
+    };
+
+    let deadband = NumericDeadband(0.5);
+    assert!(!deadband.is_significant(&value(1.0, 192), &value(1.4, 192)));
+    assert!(deadband.is_significant(&value(1.0, 192), &value(1.6, 192)));
+    assert!(deadband.is_significant(&value(1.0, 192), &value(1.0, 0)));
+    assert!(deadband.is_significant(&value(1.0, 192), &value(f64::NAN, 192)));
+
+    assert!(!AnyChange.is_significant(&value(1.0, 192), &value(1.0, 192)));
+    assert!(AnyChange.is_significant(&value(1.0, 192), &value(1.4, 192)));
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_refresh_resolves_without_raw_receiver() {
+    let object = crate::server::mock::MockGroup::new("Group", 7, 1).into_object();
+    let unknown: windows::core::IUnknown = object.to_interface();
+    let mut group = Box::new(Group::from(
+        crate::client::v2::Group::try_from(unknown).expect("Failed to cast mock group"),
+    ));
+    assert!(group.refresh(DataSourceTarget::ForceCache).is_err());
+
+    group.initialize().expect("Failed to advise data callback");
+    let results = group
+        .add(vec![
+            ItemDef::builder().item_id("Tag.Speed").build().unwrap(),
+        ])
+        .expect("Failed to add item");
+    assert!(results[0].is_ok());
+
+    let refresh = group
+        .refresh(DataSourceTarget::ForceCache)
+        .expect("Failed to request refresh");
+    // The mock completes refreshes only when asked, as a server's update loop would.
+    crate::server::traits::GroupTrait::complete_refreshes(&object.0);
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+    let event = runtime
+        .block_on(tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            refresh,
+        ))
+        .expect("Refresh did not resolve")
+        .expect("Refresh failed");
+    assert_eq!(event.client_items.len(), 1);
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_subscription_update_accepted() {
+    let object = crate::server::mock::MockGroup::new("Group", 7, 1).into_object();
+    let unknown: windows::core::IUnknown = object.to_interface();
+    let mut group = Box::new(Group::from(
+        crate::client::v2::Group::try_from(unknown).expect("Failed to cast mock group"),
+    ));
+    group.initialize().expect("Failed to advise data callback");
+    let item = ItemDef::builder().item_id("Tag.Speed").build().unwrap();
+    group.add(vec![item]).expect("Failed to add item");
+
+    // Pushed values carry transaction id 0 and have no awaiter.
+    object
+        .push_value("Tag.Speed", Variant::F64(1.5), 0xC0)
+        .expect("Subscription update rejected");
+}
+
+#[test]
+fn test_call_timeout_needs_single_threaded_apartment() {
+    std::thread::spawn(|| {
+        let client = Guard::new(Client::v2()).expect("Failed to create client guard");
+        assert!(
+            client
+                .set_call_timeout(std::time::Duration::from_secs(1))
+                .is_err()
+        );
+    })
+    .join()
+    .unwrap();
+
+    std::thread::spawn(|| {
+        let client =
+            Guard::new_single_threaded(Client::v2()).expect("Failed to create client guard");
+        client
+            .set_call_timeout(std::time::Duration::from_secs(1))
+            .expect("Failed to set call timeout");
+        client.clear_call_timeout().unwrap();
+    })
+    .join()
+    .unwrap();
+}
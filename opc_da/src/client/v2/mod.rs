@@ -38,6 +38,8 @@ pub struct Server {
     pub(crate) item_properties: opc_da_bindings::IOPCItemProperties,
     pub(crate) server_public_groups: Option<opc_da_bindings::IOPCServerPublicGroups>,
     pub(crate) browse_server_address_space: Option<opc_da_bindings::IOPCBrowseServerAddressSpace>,
+    #[cfg_attr(not(feature = "async"), allow(dead_code))]
+    pub(crate) affinity: super::unified::ThreadAffinity,
 }
 
 impl TryFrom<windows::core::IUnknown> for Server {
@@ -51,6 +53,7 @@ impl TryFrom<windows::core::IUnknown> for Server {
             item_properties: value.cast()?,
             server_public_groups: value.cast().ok(),
             browse_server_address_space: value.cast().ok(),
+            affinity: super::unified::ThreadAffinity::current(),
         })
     }
 }
@@ -10,7 +10,7 @@ use super::{
     traits::{
         AsyncIo2Trait, AsyncIo3Trait, BrowseTrait, CommonTrait, ConnectionPointContainerTrait,
         GroupStateMgt2Trait, GroupStateMgtTrait, ItemDeadbandMgtTrait, ItemIoTrait, ItemMgtTrait,
-        ItemSamplingMgtTrait, ServerTrait, SyncIo2Trait, SyncIoTrait,
+        ItemPropertiesTrait, ItemSamplingMgtTrait, ServerTrait, SyncIo2Trait, SyncIoTrait,
     },
 };
 
@@ -29,12 +29,16 @@ impl ClientTrait<Server> for Client {
 /// - `IOPCCommon` for server status and locale management
 /// - `IOPCBrowse` for browsing the server address space
 /// - `IOPCItemIO` for direct item read/write operations
+/// - `IOPCItemProperties` for item properties, if kept for 2.0 compatibility
 pub struct Server {
     pub(crate) server: opc_da_bindings::IOPCServer,
     pub(crate) common: opc_comn_bindings::IOPCCommon,
     pub(crate) connection_point_container: windows::Win32::System::Com::IConnectionPointContainer,
     pub(crate) browse: opc_da_bindings::IOPCBrowse,
     pub(crate) item_io: opc_da_bindings::IOPCItemIO,
+    pub(crate) item_properties: Option<opc_da_bindings::IOPCItemProperties>,
+    #[cfg_attr(not(feature = "async"), allow(dead_code))]
+    pub(crate) affinity: super::unified::ThreadAffinity,
 }
 
 impl TryFrom<windows::core::IUnknown> for Server {
@@ -47,6 +51,8 @@ impl TryFrom<windows::core::IUnknown> for Server {
             connection_point_container: value.cast()?,
             browse: value.cast()?,
             item_io: value.cast()?,
+            item_properties: value.cast().ok(),
+            affinity: super::unified::ThreadAffinity::current(),
         })
     }
 }
@@ -83,6 +89,17 @@ impl ItemIoTrait for Server {
     }
 }
 
+impl ItemPropertiesTrait for Server {
+    fn interface(&self) -> windows::core::Result<&opc_da_bindings::IOPCItemProperties> {
+        self.item_properties.as_ref().ok_or_else(|| {
+            windows::core::Error::new(
+                windows::Win32::Foundation::E_NOTIMPL,
+                "IOPCItemProperties not supported",
+            )
+        })
+    }
+}
+
 /// Iterator over OPC DA 3.0 groups.
 pub type GroupIterator = super::GroupIterator<Group>;
 
@@ -3,14 +3,116 @@ use crate::{
     utils::{IntoBridge, LocalPointer, RemoteArray, ToNative, TryFromNative, TryToNative},
 };
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Version {
     V1,
     V2,
     V3,
 }
 
+impl Version {
+    /// Returns the component category GUID servers of this version register under.
+    pub fn to_guid(&self) -> windows::core::GUID {
+        use windows::core::Interface as _;
+
+        match self {
+            Version::V1 => opc_da_bindings::CATID_OPCDAServer10::IID,
+            Version::V2 => opc_da_bindings::CATID_OPCDAServer20::IID,
+            Version::V3 => opc_da_bindings::CATID_OPCDAServer30::IID,
+        }
+    }
+}
+
+/// Selects servers by the OPC DA versions they register for.
+///
+/// The two lists map to the component categories passed to
+/// `IOPCServerList::EnumClassesOfCategories`:
+///
+/// - `available_versions` (`rgcatidImpl`): a server is listed if it
+///   implements at least one of these categories.
+/// - `requires_versions` (`rgcatidReq`): the categories the client supports.
+///   A server is only listed if every category it requires is among them,
+///   so a server requiring a version missing here is filtered out.
+///
+/// An empty `requires_versions` lists only servers that require nothing.
+/// [`da_any`](Self::da_any) is usually what you want:
+///
+/// ```ignore
+/// let filter = ServerFilter::da_any();
+/// let filter = ServerFilter::default().implements(Version::V2).requires(Version::V2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ServerFilter {
+    /// Servers implementing at least one of these versions are listed.
+    pub available_versions: Vec<Version>,
+    /// Versions a listed server may require, passed as `rgcatidReq`.
+    pub requires_versions: Vec<Version>,
+}
+
+impl ServerFilter {
+    /// Also lists servers implementing `version`.
+    pub fn implements(mut self, version: Version) -> Self {
+        if !self.available_versions.contains(&version) {
+            self.available_versions.push(version);
+        }
+        self
+    }
+
+    /// Accepts servers that require `version`.
+    ///
+    /// Servers requiring a version that was not accepted are not listed.
+    pub fn requires(mut self, version: Version) -> Self {
+        if !self.requires_versions.contains(&version) {
+            self.requires_versions.push(version);
+        }
+        self
+    }
+
+    /// Returns the implemented and required category IDs.
+    #[cfg(feature = "unstable_client")]
+    pub(crate) fn category_ids(&self) -> (Vec<windows::core::GUID>, Vec<windows::core::GUID>) {
+        (
+            self.available_versions
+                .iter()
+                .map(Version::to_guid)
+                .collect(),
+            self.requires_versions
+                .iter()
+                .map(Version::to_guid)
+                .collect(),
+        )
+    }
+
+    /// Lists OPC DA 1.0 servers.
+    pub fn da1() -> Self {
+        Self::versions(vec![Version::V1])
+    }
+
+    /// Lists OPC DA 2.0 servers.
+    pub fn da2() -> Self {
+        Self::versions(vec![Version::V2])
+    }
+
+    /// Lists OPC DA 3.0 servers.
+    pub fn da3() -> Self {
+        Self::versions(vec![Version::V3])
+    }
+
+    /// Lists servers of any OPC DA version.
+    pub fn da_any() -> Self {
+        Self::versions(vec![Version::V1, Version::V2, Version::V3])
+    }
+
+    fn versions(versions: Vec<Version>) -> Self {
+        Self {
+            available_versions: versions.clone(),
+            requires_versions: versions,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GroupState {
     pub update_rate: u32,
     pub active: bool,
@@ -22,6 +124,17 @@ pub struct GroupState {
     pub server_handle: u32,
 }
 
+/// Partial update of a group state, `None` fields are left unchanged.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GroupStateUpdate {
+    pub update_rate: Option<u32>,
+    pub active: Option<bool>,
+    pub time_bias: Option<i32>,
+    pub percent_deadband: Option<f32>,
+    pub locale_id: Option<u32>,
+    pub client_handle: Option<u32>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ServerStatus {
     pub start_time: std::time::SystemTime,
@@ -36,6 +149,20 @@ pub struct ServerStatus {
     pub vendor_info: String,
 }
 
+impl ServerStatus {
+    /// Sentinel reported in `band_width` when the server does not know it.
+    pub const BANDWIDTH_UNKNOWN: u32 = u32::MAX;
+
+    /// Returns the bandwidth in percent, or `None` if the server does not report it.
+    pub fn bandwidth(&self) -> Option<u32> {
+        (self.band_width != Self::BANDWIDTH_UNKNOWN).then_some(self.band_width)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.server_state == ServerState::Running
+    }
+}
+
 impl TryFromNative<opc_da_bindings::tagOPCSERVERSTATUS> for ServerStatus {
     fn try_from_native(
         native: &opc_da_bindings::tagOPCSERVERSTATUS,
@@ -56,6 +183,7 @@ impl TryFromNative<opc_da_bindings::tagOPCSERVERSTATUS> for ServerStatus {
 }
 
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ItemDef {
     pub access_path: String,
     pub item_id: String,
@@ -65,6 +193,88 @@ pub struct ItemDef {
     pub blob: Vec<u8>,
 }
 
+impl ItemDef {
+    pub fn builder() -> ItemDefBuilder {
+        ItemDefBuilder::default()
+    }
+}
+
+/// Configuration of a group and its items, enough to recreate the group on
+/// another connection or after a restart.
+///
+/// The server handle in `state` is ignored when restoring. The `serde` feature
+/// derives `Serialize` and `Deserialize` for it.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubscriptionSpec {
+    pub state: GroupState,
+    pub items: Vec<ItemDef>,
+}
+
+/// Builds an [`ItemDef`], an active item with `VT_EMPTY` (server canonical) type by default.
+#[derive(Debug, Clone)]
+pub struct ItemDefBuilder {
+    def: ItemDef,
+}
+
+impl Default for ItemDefBuilder {
+    fn default() -> Self {
+        Self {
+            def: ItemDef {
+                active: true,
+                data_type: windows::Win32::System::Variant::VT_EMPTY.0,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl ItemDefBuilder {
+    pub fn item_id(mut self, item_id: impl Into<String>) -> Self {
+        self.def.item_id = item_id.into();
+        self
+    }
+
+    pub fn access_path(mut self, access_path: impl Into<String>) -> Self {
+        self.def.access_path = access_path.into();
+        self
+    }
+
+    pub fn active(mut self, active: bool) -> Self {
+        self.def.active = active;
+        self
+    }
+
+    pub fn client_handle(mut self, client_handle: u32) -> Self {
+        self.def.client_handle = client_handle;
+        self
+    }
+
+    pub fn requested_type(mut self, requested_type: u16) -> Self {
+        self.def.data_type = requested_type;
+        self
+    }
+
+    /// Vendor specific data, e.g. the blob returned by `AddItems` or `ValidateItems`.
+    pub fn blob(mut self, blob: Vec<u8>) -> Self {
+        self.def.blob = blob;
+        self
+    }
+
+    /// # Errors
+    /// Returns E_INVALIDARG if the item ID is empty
+    pub fn build(self) -> windows::core::Result<ItemDef> {
+        if self.def.item_id.is_empty() {
+            return Err(windows::core::Error::new(
+                windows::Win32::Foundation::E_INVALIDARG,
+                "item_id is empty",
+            ));
+        }
+
+        Ok(self.def)
+    }
+}
+
 pub struct ItemDefBridge {
     pub access_path: LocalPointer<Vec<u16>>,
     pub item_id: LocalPointer<Vec<u16>>,
@@ -208,6 +418,168 @@ impl ToNative<opc_da_bindings::tagOPCENUMSCOPE> for EnumScope {
     }
 }
 
+/// Property IDs defined by the OPC DA specification.
+///
+/// IDs 1-99 are reserved for OPC specific properties, 100-4999 for
+/// recommended properties and everything from 5000 on for vendors. IDs
+/// without a standard variant convert to [`PropertyId::Vendor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyId {
+    /// Canonical data type of the item (VT_*).
+    DataType,
+    Value,
+    Quality,
+    Timestamp,
+    AccessRights,
+    /// Fastest rate at which the server can obtain data, in milliseconds.
+    ScanRate,
+    EuType,
+    EuInfo,
+    EuUnits,
+    Description,
+    /// Upper limit of the normal operating range.
+    EuHighLimit,
+    /// Lower limit of the normal operating range.
+    EuLowLimit,
+    InstrumentHighLimit,
+    InstrumentLowLimit,
+    CloseLabel,
+    OpenLabel,
+    TimeZone,
+    ConditionStatus,
+    AlarmQuickHelp,
+    AlarmAreaList,
+    PrimaryAlarmArea,
+    ConditionLogic,
+    LimitExceeded,
+    Deadband,
+    HiHiLimit,
+    HiLimit,
+    LoLimit,
+    LoLoLimit,
+    ChangeRateLimit,
+    DeviationLimit,
+    SoundFile,
+    TypeSystemId,
+    DictionaryId,
+    TypeId,
+    Dictionary,
+    TypeDescription,
+    ConsistencyWindow,
+    WriteBehavior,
+    UnconvertedItemId,
+    UnfilteredItemId,
+    DataFilterValue,
+    /// Any property ID without a standard variant.
+    Vendor(u32),
+}
+
+impl From<u32> for PropertyId {
+    fn from(value: u32) -> Self {
+        match value {
+            opc_da_bindings::OPC_PROPERTY_DATATYPE => PropertyId::DataType,
+            opc_da_bindings::OPC_PROPERTY_VALUE => PropertyId::Value,
+            opc_da_bindings::OPC_PROPERTY_QUALITY => PropertyId::Quality,
+            opc_da_bindings::OPC_PROPERTY_TIMESTAMP => PropertyId::Timestamp,
+            opc_da_bindings::OPC_PROPERTY_ACCESS_RIGHTS => PropertyId::AccessRights,
+            opc_da_bindings::OPC_PROPERTY_SCAN_RATE => PropertyId::ScanRate,
+            opc_da_bindings::OPC_PROPERTY_EU_TYPE => PropertyId::EuType,
+            opc_da_bindings::OPC_PROPERTY_EU_INFO => PropertyId::EuInfo,
+            opc_da_bindings::OPC_PROPERTY_EU_UNITS => PropertyId::EuUnits,
+            opc_da_bindings::OPC_PROPERTY_DESCRIPTION => PropertyId::Description,
+            opc_da_bindings::OPC_PROPERTY_HIGH_EU => PropertyId::EuHighLimit,
+            opc_da_bindings::OPC_PROPERTY_LOW_EU => PropertyId::EuLowLimit,
+            opc_da_bindings::OPC_PROPERTY_HIGH_IR => PropertyId::InstrumentHighLimit,
+            opc_da_bindings::OPC_PROPERTY_LOW_IR => PropertyId::InstrumentLowLimit,
+            opc_da_bindings::OPC_PROPERTY_CLOSE_LABEL => PropertyId::CloseLabel,
+            opc_da_bindings::OPC_PROPERTY_OPEN_LABEL => PropertyId::OpenLabel,
+            opc_da_bindings::OPC_PROPERTY_TIMEZONE => PropertyId::TimeZone,
+            opc_da_bindings::OPC_PROPERTY_CONDITION_STATUS => PropertyId::ConditionStatus,
+            opc_da_bindings::OPC_PROPERTY_ALARM_QUICK_HELP => PropertyId::AlarmQuickHelp,
+            opc_da_bindings::OPC_PROPERTY_ALARM_AREA_LIST => PropertyId::AlarmAreaList,
+            opc_da_bindings::OPC_PROPERTY_PRIMARY_ALARM_AREA => PropertyId::PrimaryAlarmArea,
+            opc_da_bindings::OPC_PROPERTY_CONDITION_LOGIC => PropertyId::ConditionLogic,
+            opc_da_bindings::OPC_PROPERTY_LIMIT_EXCEEDED => PropertyId::LimitExceeded,
+            opc_da_bindings::OPC_PROPERTY_DEADBAND => PropertyId::Deadband,
+            opc_da_bindings::OPC_PROPERTY_HIHI_LIMIT => PropertyId::HiHiLimit,
+            opc_da_bindings::OPC_PROPERTY_HI_LIMIT => PropertyId::HiLimit,
+            opc_da_bindings::OPC_PROPERTY_LO_LIMIT => PropertyId::LoLimit,
+            opc_da_bindings::OPC_PROPERTY_LOLO_LIMIT => PropertyId::LoLoLimit,
+            opc_da_bindings::OPC_PROPERTY_CHANGE_RATE_LIMIT => PropertyId::ChangeRateLimit,
+            opc_da_bindings::OPC_PROPERTY_DEVIATION_LIMIT => PropertyId::DeviationLimit,
+            opc_da_bindings::OPC_PROPERTY_SOUND_FILE => PropertyId::SoundFile,
+            opc_da_bindings::OPC_PROPERTY_TYPE_SYSTEM_ID => PropertyId::TypeSystemId,
+            opc_da_bindings::OPC_PROPERTY_DICTIONARY_ID => PropertyId::DictionaryId,
+            opc_da_bindings::OPC_PROPERTY_TYPE_ID => PropertyId::TypeId,
+            opc_da_bindings::OPC_PROPERTY_DICTIONARY => PropertyId::Dictionary,
+            opc_da_bindings::OPC_PROPERTY_TYPE_DESCRIPTION => PropertyId::TypeDescription,
+            opc_da_bindings::OPC_PROPERTY_CONSISTENCY_WINDOW => PropertyId::ConsistencyWindow,
+            opc_da_bindings::OPC_PROPERTY_WRITE_BEHAVIOR => PropertyId::WriteBehavior,
+            opc_da_bindings::OPC_PROPERTY_UNCONVERTED_ITEM_ID => PropertyId::UnconvertedItemId,
+            opc_da_bindings::OPC_PROPERTY_UNFILTERED_ITEM_ID => PropertyId::UnfilteredItemId,
+            opc_da_bindings::OPC_PROPERTY_DATA_FILTER_VALUE => PropertyId::DataFilterValue,
+            other => PropertyId::Vendor(other),
+        }
+    }
+}
+
+impl From<PropertyId> for u32 {
+    fn from(value: PropertyId) -> Self {
+        match value {
+            PropertyId::DataType => opc_da_bindings::OPC_PROPERTY_DATATYPE,
+            PropertyId::Value => opc_da_bindings::OPC_PROPERTY_VALUE,
+            PropertyId::Quality => opc_da_bindings::OPC_PROPERTY_QUALITY,
+            PropertyId::Timestamp => opc_da_bindings::OPC_PROPERTY_TIMESTAMP,
+            PropertyId::AccessRights => opc_da_bindings::OPC_PROPERTY_ACCESS_RIGHTS,
+            PropertyId::ScanRate => opc_da_bindings::OPC_PROPERTY_SCAN_RATE,
+            PropertyId::EuType => opc_da_bindings::OPC_PROPERTY_EU_TYPE,
+            PropertyId::EuInfo => opc_da_bindings::OPC_PROPERTY_EU_INFO,
+            PropertyId::EuUnits => opc_da_bindings::OPC_PROPERTY_EU_UNITS,
+            PropertyId::Description => opc_da_bindings::OPC_PROPERTY_DESCRIPTION,
+            PropertyId::EuHighLimit => opc_da_bindings::OPC_PROPERTY_HIGH_EU,
+            PropertyId::EuLowLimit => opc_da_bindings::OPC_PROPERTY_LOW_EU,
+            PropertyId::InstrumentHighLimit => opc_da_bindings::OPC_PROPERTY_HIGH_IR,
+            PropertyId::InstrumentLowLimit => opc_da_bindings::OPC_PROPERTY_LOW_IR,
+            PropertyId::CloseLabel => opc_da_bindings::OPC_PROPERTY_CLOSE_LABEL,
+            PropertyId::OpenLabel => opc_da_bindings::OPC_PROPERTY_OPEN_LABEL,
+            PropertyId::TimeZone => opc_da_bindings::OPC_PROPERTY_TIMEZONE,
+            PropertyId::ConditionStatus => opc_da_bindings::OPC_PROPERTY_CONDITION_STATUS,
+            PropertyId::AlarmQuickHelp => opc_da_bindings::OPC_PROPERTY_ALARM_QUICK_HELP,
+            PropertyId::AlarmAreaList => opc_da_bindings::OPC_PROPERTY_ALARM_AREA_LIST,
+            PropertyId::PrimaryAlarmArea => opc_da_bindings::OPC_PROPERTY_PRIMARY_ALARM_AREA,
+            PropertyId::ConditionLogic => opc_da_bindings::OPC_PROPERTY_CONDITION_LOGIC,
+            PropertyId::LimitExceeded => opc_da_bindings::OPC_PROPERTY_LIMIT_EXCEEDED,
+            PropertyId::Deadband => opc_da_bindings::OPC_PROPERTY_DEADBAND,
+            PropertyId::HiHiLimit => opc_da_bindings::OPC_PROPERTY_HIHI_LIMIT,
+            PropertyId::HiLimit => opc_da_bindings::OPC_PROPERTY_HI_LIMIT,
+            PropertyId::LoLimit => opc_da_bindings::OPC_PROPERTY_LO_LIMIT,
+            PropertyId::LoLoLimit => opc_da_bindings::OPC_PROPERTY_LOLO_LIMIT,
+            PropertyId::ChangeRateLimit => opc_da_bindings::OPC_PROPERTY_CHANGE_RATE_LIMIT,
+            PropertyId::DeviationLimit => opc_da_bindings::OPC_PROPERTY_DEVIATION_LIMIT,
+            PropertyId::SoundFile => opc_da_bindings::OPC_PROPERTY_SOUND_FILE,
+            PropertyId::TypeSystemId => opc_da_bindings::OPC_PROPERTY_TYPE_SYSTEM_ID,
+            PropertyId::DictionaryId => opc_da_bindings::OPC_PROPERTY_DICTIONARY_ID,
+            PropertyId::TypeId => opc_da_bindings::OPC_PROPERTY_TYPE_ID,
+            PropertyId::Dictionary => opc_da_bindings::OPC_PROPERTY_DICTIONARY,
+            PropertyId::TypeDescription => opc_da_bindings::OPC_PROPERTY_TYPE_DESCRIPTION,
+            PropertyId::ConsistencyWindow => opc_da_bindings::OPC_PROPERTY_CONSISTENCY_WINDOW,
+            PropertyId::WriteBehavior => opc_da_bindings::OPC_PROPERTY_WRITE_BEHAVIOR,
+            PropertyId::UnconvertedItemId => opc_da_bindings::OPC_PROPERTY_UNCONVERTED_ITEM_ID,
+            PropertyId::UnfilteredItemId => opc_da_bindings::OPC_PROPERTY_UNFILTERED_ITEM_ID,
+            PropertyId::DataFilterValue => opc_da_bindings::OPC_PROPERTY_DATA_FILTER_VALUE,
+            PropertyId::Vendor(id) => id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AvailableProperty {
+    pub property_id: u32,
+    pub description: String,
+    pub data_type: u16,
+}
+
+#[derive(Clone)]
 pub struct ItemAttributes {
     pub access_path: String,
     pub item_id: String,
@@ -244,12 +616,37 @@ impl TryFromNative<opc_da_bindings::tagOPCITEMATTRIBUTES> for ItemAttributes {
     }
 }
 
+/// Builds the definition that re-adds an enumerated item to another group.
+impl From<&ItemAttributes> for ItemDef {
+    fn from(attributes: &ItemAttributes) -> Self {
+        Self {
+            access_path: attributes.access_path.clone(),
+            item_id: attributes.item_id.clone(),
+            active: attributes.active,
+            client_handle: attributes.client_handle,
+            data_type: attributes.requested_data_type,
+            blob: attributes.blob.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EuType {
     NoEnum,
     Analog,
     Enumerated,
 }
 
+impl ToNative<opc_da_bindings::tagOPCEUTYPE> for EuType {
+    fn to_native(&self) -> opc_da_bindings::tagOPCEUTYPE {
+        match self {
+            EuType::NoEnum => opc_da_bindings::OPC_NOENUM,
+            EuType::Analog => opc_da_bindings::OPC_ANALOG,
+            EuType::Enumerated => opc_da_bindings::OPC_ENUMERATED,
+        }
+    }
+}
+
 impl TryFromNative<opc_da_bindings::tagOPCEUTYPE> for EuType {
     fn try_from_native(native: &opc_da_bindings::tagOPCEUTYPE) -> windows::core::Result<Self> {
         match *native {
@@ -264,6 +661,106 @@ impl TryFromNative<opc_da_bindings::tagOPCEUTYPE> for EuType {
     }
 }
 
+/// Decoded engineering units information of an item.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EuInfo {
+    /// Range of an analog item, with the units of property 100 when read
+    /// through `Server::engineering_units` of the unified client.
+    Analog {
+        low: f64,
+        high: f64,
+        units: Option<String>,
+    },
+    /// Labels of the states of an enumerated item, indexed by value.
+    Enumerated(Vec<String>),
+}
+
+impl EuInfo {
+    /// Decodes `eu_info`, as in `ItemAttributes::eu_info` or property 8.
+    ///
+    /// Analog items carry a `VT_ARRAY | VT_R8` with the low and high limits,
+    /// enumerated items a `VT_ARRAY | VT_BSTR` of state labels.
+    ///
+    /// # Returns
+    /// `None` for `EuType::NoEnum`
+    ///
+    /// # Errors
+    /// Returns `DISP_E_TYPEMISMATCH` if `eu_info` does not match `eu_type`.
+    pub fn from_native(
+        eu_type: &EuType,
+        eu_info: &windows::Win32::System::Variant::VARIANT,
+    ) -> windows::core::Result<Option<Self>> {
+        match eu_type {
+            EuType::NoEnum => Ok(None),
+            EuType::Analog => {
+                let limits: Vec<f64> = unsafe {
+                    safe_array_elements(eu_info, windows::Win32::System::Variant::VT_R8)?
+                };
+                match limits.as_slice() {
+                    &[low, high] => Ok(Some(EuInfo::Analog {
+                        low,
+                        high,
+                        units: None,
+                    })),
+                    _ => Err(windows::core::Error::new(
+                        windows::Win32::Foundation::DISP_E_TYPEMISMATCH,
+                        format!("Expected 2 analog EU limits, got {}", limits.len()),
+                    )),
+                }
+            }
+            EuType::Enumerated => {
+                let labels: Vec<windows::core::BSTR> = unsafe {
+                    safe_array_elements(eu_info, windows::Win32::System::Variant::VT_BSTR)?
+                };
+                Ok(Some(EuInfo::Enumerated(
+                    labels.iter().map(ToString::to_string).collect(),
+                )))
+            }
+        }
+    }
+}
+
+/// Copies the elements of a one-dimensional `VT_ARRAY | vt` variant.
+///
+/// # Safety
+/// `T` must be the Rust type of `vt`, elements are copied with `SafeArrayGetElement`.
+unsafe fn safe_array_elements<T: Default>(
+    variant: &windows::Win32::System::Variant::VARIANT,
+    vt: windows::Win32::System::Variant::VARENUM,
+) -> windows::core::Result<Vec<T>> {
+    use windows::Win32::System::Ole::{
+        SafeArrayGetDim, SafeArrayGetElement, SafeArrayGetLBound, SafeArrayGetUBound,
+    };
+
+    let expected = windows::Win32::System::Variant::VT_ARRAY.0 | vt.0;
+    let (actual, array) = unsafe {
+        (
+            variant.Anonymous.Anonymous.vt.0,
+            variant.Anonymous.Anonymous.Anonymous.parray,
+        )
+    };
+    if actual != expected || array.is_null() || unsafe { SafeArrayGetDim(array) } != 1 {
+        return Err(windows::core::Error::new(
+            windows::Win32::Foundation::DISP_E_TYPEMISMATCH,
+            format!(
+                "Expected a one-dimensional array of VT {}, got VT {actual}",
+                vt.0
+            ),
+        ));
+    }
+
+    let (lower, upper) = unsafe { (SafeArrayGetLBound(array, 1)?, SafeArrayGetUBound(array, 1)?) };
+    (lower..=upper)
+        .map(|index| {
+            let mut element = T::default();
+            unsafe {
+                SafeArrayGetElement(array, &index, (&mut element as *mut T).cast())?;
+            }
+            Ok(element)
+        })
+        .collect()
+}
+
 pub struct ItemState {
     pub client_handle: u32,
     pub timestamp: std::time::SystemTime,
@@ -289,6 +786,7 @@ pub enum DataSourceTarget {
 }
 
 impl DataSourceTarget {
+    /// Returns the OPC DA 3.0 max age, `u32::MAX` for the cache and 0 for the device.
     pub fn max_age(&self) -> u32 {
         match self {
             DataSourceTarget::WithMaxAge(max_age) => *max_age,
@@ -296,6 +794,11 @@ impl DataSourceTarget {
             DataSourceTarget::ForceDevice => 0,
         }
     }
+
+    /// Returns the max age of [`max_age`](Self::max_age) for `count` items.
+    pub fn to_max_ages(&self, count: usize) -> Vec<u32> {
+        vec![self.max_age(); count]
+    }
 }
 
 impl TryFromNative<opc_da_bindings::tagOPCDATASOURCE> for DataSourceTarget {
@@ -311,12 +814,15 @@ impl TryFromNative<opc_da_bindings::tagOPCDATASOURCE> for DataSourceTarget {
     }
 }
 
+/// Derived from [`DataSourceTarget::max_age`], so both versions agree on what
+/// the cache and the device mean. Only the max ages of the cache and the device
+/// have an OPC DA 1.0/2.0 equivalent.
 impl TryToNative<opc_da_bindings::tagOPCDATASOURCE> for DataSourceTarget {
     fn try_to_native(&self) -> windows::core::Result<opc_da_bindings::tagOPCDATASOURCE> {
-        match self {
-            DataSourceTarget::ForceCache => Ok(opc_da_bindings::OPC_DS_CACHE),
-            DataSourceTarget::ForceDevice => Ok(opc_da_bindings::OPC_DS_DEVICE),
-            DataSourceTarget::WithMaxAge(_) => Err(windows::core::Error::new(
+        match self.max_age() {
+            u32::MAX => Ok(opc_da_bindings::OPC_DS_CACHE),
+            0 => Ok(opc_da_bindings::OPC_DS_DEVICE),
+            _ => Err(windows::core::Error::new(
                 windows::Win32::Foundation::E_INVALIDARG,
                 "MaxAge data source requires a value",
             )),
@@ -330,6 +836,78 @@ pub struct ItemValue {
     pub timestamp: std::time::SystemTime,
 }
 
+/// Per-item results of a read, in request order.
+///
+/// The call-level error is already handled by the time this is returned,
+/// each entry holds the item-level result.
+pub struct ReadResults(pub Vec<windows::core::Result<ItemValue>>);
+
+impl ReadResults {
+    /// Returns the successfully read values.
+    pub fn oks(&self) -> impl Iterator<Item = &ItemValue> {
+        self.0.iter().filter_map(|result| result.as_ref().ok())
+    }
+
+    /// Returns the failed items as `(index, error)`.
+    pub fn errors(&self) -> impl Iterator<Item = (usize, &windows::core::Error)> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(|(index, result)| result.as_ref().err().map(|error| (index, error)))
+    }
+
+    /// Returns `true` if every item was read successfully.
+    pub fn all_ok(&self) -> bool {
+        self.0.iter().all(|result| result.is_ok())
+    }
+
+    /// Pairs the results with the requested names.
+    ///
+    /// # Returns
+    /// The values that were read by name, and the names that failed with
+    /// their errors in request order.
+    ///
+    /// # Errors
+    /// Returns `E_INVALIDARG` if the number of names differs from the number
+    /// of results.
+    #[allow(clippy::type_complexity)]
+    pub fn into_map<S: AsRef<str>>(
+        self,
+        names: &[S],
+    ) -> windows::core::Result<(
+        std::collections::HashMap<String, ItemValue>,
+        Vec<(String, windows::core::Error)>,
+    )> {
+        if names.len() != self.0.len() {
+            return Err(windows::core::Error::new(
+                windows::Win32::Foundation::E_INVALIDARG,
+                "names and results must have the same length",
+            ));
+        }
+
+        let mut values = std::collections::HashMap::new();
+        let mut errors = vec![];
+        for (name, result) in names.iter().zip(self.0) {
+            match result {
+                Ok(value) => {
+                    values.insert(name.as_ref().to_string(), value);
+                }
+                Err(error) => errors.push((name.as_ref().to_string(), error)),
+            }
+        }
+
+        Ok((values, errors))
+    }
+}
+
+impl From<Vec<windows::core::Result<ItemValue>>> for ReadResults {
+    fn from(results: Vec<windows::core::Result<ItemValue>>) -> Self {
+        Self(results)
+    }
+}
+
+/// Copies the values, the arrays keep owning the originals. Use
+/// `extend_item_values` to move them out instead.
 impl
     TryFromNative<(
         RemoteArray<windows::Win32::System::Variant::VARIANT>,
@@ -347,16 +925,7 @@ impl
         ),
     ) -> windows::core::Result<Self> {
         let (values, qualities, timestamps, errors) = native;
-
-        if values.len() != qualities.len()
-            || values.len() != timestamps.len()
-            || values.len() != errors.len()
-        {
-            return Err(windows::core::Error::new(
-                windows::Win32::Foundation::E_INVALIDARG,
-                "Arrays have different lengths",
-            ));
-        }
+        check_item_value_lengths(values, qualities, timestamps, errors)?;
 
         Ok(values
             .as_slice()
@@ -365,42 +934,190 @@ impl
             .zip(timestamps.as_slice())
             .zip(errors.as_slice())
             .map(|(((value, quality), timestamp), error)| {
-                if error.is_ok() {
-                    Ok(ItemValue {
-                        value: value.clone(),
-                        quality: *quality,
-                        timestamp: try_from_native!(timestamp),
-                    })
-                } else {
-                    Err((*error).into())
-                }
+                item_value(value.clone(), quality, timestamp, error)
             })
             .collect())
     }
 }
 
+fn item_value(
+    value: windows::Win32::System::Variant::VARIANT,
+    quality: &u16,
+    timestamp: &windows::Win32::Foundation::FILETIME,
+    error: &windows::core::HRESULT,
+) -> windows::core::Result<ItemValue> {
+    if error.is_ok() {
+        Ok(ItemValue {
+            value,
+            quality: *quality,
+            timestamp: try_from_native!(timestamp),
+        })
+    } else {
+        Err((*error).into())
+    }
+}
+
+fn check_item_value_lengths(
+    values: &RemoteArray<windows::Win32::System::Variant::VARIANT>,
+    qualities: &RemoteArray<u16>,
+    timestamps: &RemoteArray<windows::Win32::Foundation::FILETIME>,
+    errors: &RemoteArray<windows::core::HRESULT>,
+) -> windows::core::Result<()> {
+    if values.len() != qualities.len()
+        || values.len() != timestamps.len()
+        || values.len() != errors.len()
+    {
+        return Err(windows::core::Error::new(
+            windows::Win32::Foundation::E_INVALIDARG,
+            "Arrays have different lengths",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Appends the values returned by `ReadMaxAge` to `out`, which keeps its
+/// allocation between calls.
+///
+/// The values are moved out of `native`, so dropping the arrays afterwards
+/// frees everything the server allocated.
+#[cfg(feature = "async")]
+pub(crate) fn extend_item_values(
+    out: &mut Vec<windows::core::Result<ItemValue>>,
+    native: &mut (
+        RemoteArray<windows::Win32::System::Variant::VARIANT>,
+        RemoteArray<u16>,
+        RemoteArray<windows::Win32::Foundation::FILETIME>,
+        RemoteArray<windows::core::HRESULT>,
+    ),
+) -> windows::core::Result<()> {
+    let (values, qualities, timestamps, errors) = native;
+    check_item_value_lengths(values, qualities, timestamps, errors)?;
+
+    out.extend(
+        values
+            .as_mut_slice()
+            .iter_mut()
+            .zip(qualities.as_slice())
+            .zip(timestamps.as_slice())
+            .zip(errors.as_slice())
+            .map(|(((value, quality), timestamp), error)| {
+                // Taking the value leaves `VT_EMPTY` behind, error or not.
+                item_value(core::mem::take(value), quality, timestamp, error)
+            }),
+    );
+
+    Ok(())
+}
+
+/// Value, quality and timestamp of an item, decoded from its properties 2, 3 and 4.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vqt {
+    pub value: Variant,
+    pub quality: u16,
+    pub timestamp: std::time::SystemTime,
+}
+
+impl Vqt {
+    /// Decodes the values of the value, quality and timestamp properties.
+    ///
+    /// The quality may be a `VT_I2`, `VT_UI2` or `VT_I4`. The timestamp may be
+    /// a `VT_DATE` or a `VT_FILETIME` stored in the 64-bit member, as servers
+    /// differ.
+    ///
+    /// # Errors
+    /// Returns `DISP_E_TYPEMISMATCH` for other quality or timestamp types and
+    /// `E_INVALIDARG` for a timestamp before the UNIX epoch.
+    pub fn from_properties(
+        value: &windows::Win32::System::Variant::VARIANT,
+        quality: &windows::Win32::System::Variant::VARIANT,
+        timestamp: &windows::Win32::System::Variant::VARIANT,
+    ) -> windows::core::Result<Self> {
+        let mismatch = |name: &str, vt: windows::Win32::System::Variant::VARENUM| {
+            windows::core::Error::new(
+                windows::Win32::Foundation::DISP_E_TYPEMISMATCH,
+                format!("Unsupported {name} property type {}", vt.0),
+            )
+        };
+
+        let quality = match Variant::from(quality) {
+            Variant::I16(quality) => quality as u16,
+            Variant::U16(quality) => quality,
+            Variant::I32(quality) => quality as u16,
+            _ => return Err(mismatch("quality", quality.vt())),
+        };
+
+        let timestamp = unsafe {
+            let inner = &timestamp.Anonymous.Anonymous;
+            match inner.vt {
+                windows::Win32::System::Variant::VT_DATE => {
+                    // OLE dates count days from 1899-12-30, 25569 days before the UNIX epoch.
+                    let seconds = (inner.Anonymous.date - 25569.0) * 86400.0;
+                    if seconds < 0.0 {
+                        return Err(windows::core::Error::new(
+                            windows::Win32::Foundation::E_INVALIDARG,
+                            "DATE is before UNIX_EPOCH",
+                        ));
+                    }
+                    std::time::UNIX_EPOCH
+                        + std::time::Duration::try_from_secs_f64(seconds)
+                            .map_err(|e| crate::error::invalid_arg(e.to_string()))?
+                }
+                windows::Win32::System::Variant::VT_FILETIME => {
+                    let filetime = inner.Anonymous.ullVal;
+                    try_from_native!(&windows::Win32::Foundation::FILETIME {
+                        dwLowDateTime: filetime as u32,
+                        dwHighDateTime: (filetime >> 32) as u32,
+                    })
+                }
+                vt => return Err(mismatch("timestamp", vt)),
+            }
+        };
+
+        Ok(Self {
+            value: Variant::from(value),
+            quality,
+            timestamp,
+        })
+    }
+}
+
+#[derive(Clone)]
 pub struct ItemPartialValue {
     pub value: windows::Win32::System::Variant::VARIANT,
     pub quality: Option<u16>,
     pub timestamp: Option<std::time::SystemTime>,
 }
 
+/// Builds a `tagOPCITEMVQT`, shared by the client and the server.
+///
+/// The `*Specified` flags are derived from the options and the reserved
+/// fields are zeroed.
+#[cfg(any(feature = "unstable_client", feature = "unstable_server"))]
+pub(crate) fn item_vqt_to_native(
+    value: windows::Win32::System::Variant::VARIANT,
+    quality: Option<u16>,
+    timestamp: Option<std::time::SystemTime>,
+) -> windows::core::Result<opc_da_bindings::tagOPCITEMVQT> {
+    Ok(opc_da_bindings::tagOPCITEMVQT {
+        vDataValue: value,
+        bQualitySpecified: quality.is_some().into(),
+        wQuality: quality.unwrap_or_default(),
+        wReserved: 0,
+        bTimeStampSpecified: timestamp.is_some().into(),
+        dwReserved: 0,
+        ftTimeStamp: timestamp
+            .map(|t| t.try_to_native())
+            .transpose()?
+            .unwrap_or_default(),
+    })
+}
+
 // try to native
+#[cfg(any(feature = "unstable_client", feature = "unstable_server"))]
 impl TryToNative<opc_da_bindings::tagOPCITEMVQT> for ItemPartialValue {
     fn try_to_native(&self) -> windows::core::Result<opc_da_bindings::tagOPCITEMVQT> {
-        Ok(opc_da_bindings::tagOPCITEMVQT {
-            vDataValue: self.value.clone(),
-            bQualitySpecified: self.quality.is_some().into(),
-            wQuality: self.quality.unwrap_or_default(),
-            bTimeStampSpecified: self.timestamp.is_some().into(),
-            ftTimeStamp: self
-                .timestamp
-                .map(|t| t.try_to_native())
-                .transpose()?
-                .unwrap_or_default(),
-            wReserved: 0,
-            dwReserved: 0,
-        })
+        item_vqt_to_native(self.value.clone(), self.quality, self.timestamp)
     }
 }
 
@@ -466,6 +1183,32 @@ impl ToNative<opc_da_bindings::tagOPCBROWSEFILTER> for BrowseFilter {
     }
 }
 
+/// An element of the address space returned by `IOPCBrowse::Browse`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowseElement {
+    /// Short name displayed in a browse tree.
+    pub name: String,
+    /// Fully qualified item ID, usable to add the item when `is_item` is set.
+    pub item_id: String,
+    /// The element is a branch with children.
+    pub has_children: bool,
+    /// The element is an item that can be added to a group.
+    pub is_item: bool,
+}
+
+impl TryFromNative<opc_da_bindings::tagOPCBROWSEELEMENT> for BrowseElement {
+    fn try_from_native(
+        native: &opc_da_bindings::tagOPCBROWSEELEMENT,
+    ) -> windows::core::Result<Self> {
+        Ok(Self {
+            name: try_from_native!(&native.szName),
+            item_id: try_from_native!(&native.szItemID),
+            has_children: native.dwFlagValue & opc_da_bindings::OPC_BROWSE_HASCHILDREN != 0,
+            is_item: native.dwFlagValue & opc_da_bindings::OPC_BROWSE_ISITEM != 0,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum DataCallbackEvent {
     DataChange(DataChangeEvent),
@@ -487,6 +1230,40 @@ pub struct DataChangeEvent {
     pub errors: RemoteArray<windows_core::HRESULT>,
 }
 
+/// A data change event that owns its values, produced by polling or decoded
+/// from a [`DataChangeEvent`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnedDataChangeEvent {
+    pub transaction_id: u32,
+    pub group_handle: u32,
+    pub client_items: Vec<u32>,
+    pub values: Vec<Variant>,
+    pub qualities: Vec<u16>,
+    pub timestamps: Vec<std::time::SystemTime>,
+    pub errors: Vec<windows_core::HRESULT>,
+}
+
+impl TryFrom<&DataChangeEvent> for OwnedDataChangeEvent {
+    type Error = windows::core::Error;
+
+    fn try_from(event: &DataChangeEvent) -> windows::core::Result<Self> {
+        Ok(Self {
+            transaction_id: event.transaction_id,
+            group_handle: event.group_handle,
+            client_items: event.client_items.as_slice().to_vec(),
+            values: event.values.as_slice().iter().map(Variant::from).collect(),
+            qualities: event.qualities.as_slice().to_vec(),
+            timestamps: event
+                .timestamps
+                .as_slice()
+                .iter()
+                .map(std::time::SystemTime::try_from_native)
+                .collect::<windows::core::Result<_>>()?,
+            errors: event.errors.as_slice().to_vec(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ReadCompleteEvent {
     pub transaction_id: u32,
@@ -750,3 +1527,452 @@ impl ToNative<windows::Win32::System::Com::CLSCTX> for ClassContext {
         }
     }
 }
+
+/// An owned, decoded `VARIANT` value.
+///
+/// Equality and hashing compare the variant kind and the decoded value.
+/// Floating-point values compare by their bit pattern, so a NaN equals a NaN
+/// with identical bits and `0.0` differs from `-0.0`. This keeps `Eq` and
+/// `Hash` consistent and reports a value that stays NaN as unchanged.
+#[derive(Debug, Clone, Default)]
+pub enum Variant {
+    #[default]
+    Empty,
+    Null,
+    Bool(bool),
+    String(String),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    /// A `VT_ERROR` value, an error code reported in place of the value.
+    Error(windows::core::HRESULT),
+}
+
+impl Variant {
+    /// Returns the `VT_*` data type of the variant.
+    pub fn get_data_type(&self) -> u16 {
+        match self {
+            Variant::Empty => windows::Win32::System::Variant::VT_EMPTY,
+            Variant::Null => windows::Win32::System::Variant::VT_NULL,
+            Variant::Bool(_) => windows::Win32::System::Variant::VT_BOOL,
+            Variant::String(_) => windows::Win32::System::Variant::VT_BSTR,
+            Variant::I8(_) => windows::Win32::System::Variant::VT_I1,
+            Variant::I16(_) => windows::Win32::System::Variant::VT_I2,
+            Variant::I32(_) => windows::Win32::System::Variant::VT_I4,
+            Variant::I64(_) => windows::Win32::System::Variant::VT_I8,
+            Variant::F32(_) => windows::Win32::System::Variant::VT_R4,
+            Variant::F64(_) => windows::Win32::System::Variant::VT_R8,
+            Variant::U8(_) => windows::Win32::System::Variant::VT_UI1,
+            Variant::U16(_) => windows::Win32::System::Variant::VT_UI2,
+            Variant::U32(_) => windows::Win32::System::Variant::VT_UI4,
+            Variant::U64(_) => windows::Win32::System::Variant::VT_UI8,
+            Variant::Error(_) => windows::Win32::System::Variant::VT_ERROR,
+        }
+        .0
+    }
+
+    /// Converts a numeric variant into `data_type` without losing data.
+    ///
+    /// Variants already of `data_type`, `Empty`, `Null` and a `VT_EMPTY`
+    /// target are returned unchanged. Floats convert to integers only when
+    /// they are integral and in range, and integers to floats only when
+    /// exactly representable.
+    ///
+    /// # Errors
+    /// Returns `DISP_E_OVERFLOW` if the value does not fit `data_type` and
+    /// `DISP_E_TYPEMISMATCH` for non-numeric conversions.
+    pub fn coerce_to(&self, data_type: u16) -> windows::core::Result<Variant> {
+        use windows::Win32::System::Variant::{
+            VT_EMPTY, VT_I1, VT_I2, VT_I4, VT_I8, VT_R4, VT_R8, VT_UI1, VT_UI2, VT_UI4, VT_UI8,
+        };
+
+        enum Number {
+            Integer(i128),
+            Float(f64),
+        }
+
+        if data_type == VT_EMPTY.0
+            || data_type == self.get_data_type()
+            || matches!(self, Variant::Empty | Variant::Null)
+        {
+            return Ok(self.clone());
+        }
+
+        let mismatch = || {
+            windows::core::Error::new(
+                windows::Win32::Foundation::DISP_E_TYPEMISMATCH,
+                format!("Cannot convert {self:?} to VT {data_type}"),
+            )
+        };
+        let overflow = || {
+            windows::core::Error::new(
+                windows::Win32::Foundation::DISP_E_OVERFLOW,
+                format!("{self:?} does not fit VT {data_type}"),
+            )
+        };
+
+        let number = match *self {
+            Variant::I8(value) => Number::Integer(value.into()),
+            Variant::I16(value) => Number::Integer(value.into()),
+            Variant::I32(value) => Number::Integer(value.into()),
+            Variant::I64(value) => Number::Integer(value.into()),
+            Variant::U8(value) => Number::Integer(value.into()),
+            Variant::U16(value) => Number::Integer(value.into()),
+            Variant::U32(value) => Number::Integer(value.into()),
+            Variant::U64(value) => Number::Integer(value.into()),
+            Variant::F32(value) => Number::Float(value.into()),
+            Variant::F64(value) => Number::Float(value),
+            _ => return Err(mismatch()),
+        };
+
+        let integer = || match number {
+            Number::Integer(value) => Ok(value),
+            // The range check keeps the cast exact, larger values overflow every target.
+            Number::Float(value) if value.fract() == 0.0 && value.abs() < 2f64.powi(127) => {
+                Ok(value as i128)
+            }
+            Number::Float(_) => Err(overflow()),
+        };
+        let float = || match number {
+            Number::Integer(value) if (value as f64) as i128 == value => Ok(value as f64),
+            Number::Integer(_) => Err(overflow()),
+            Number::Float(value) => Ok(value),
+        };
+
+        Ok(match windows::Win32::System::Variant::VARENUM(data_type) {
+            VT_I1 => Variant::I8(integer()?.try_into().map_err(|_| overflow())?),
+            VT_I2 => Variant::I16(integer()?.try_into().map_err(|_| overflow())?),
+            VT_I4 => Variant::I32(integer()?.try_into().map_err(|_| overflow())?),
+            VT_I8 => Variant::I64(integer()?.try_into().map_err(|_| overflow())?),
+            VT_UI1 => Variant::U8(integer()?.try_into().map_err(|_| overflow())?),
+            VT_UI2 => Variant::U16(integer()?.try_into().map_err(|_| overflow())?),
+            VT_UI4 => Variant::U32(integer()?.try_into().map_err(|_| overflow())?),
+            VT_UI8 => Variant::U64(integer()?.try_into().map_err(|_| overflow())?),
+            VT_R4 => {
+                let value = float()?;
+                let narrowed = value as f32;
+                if !value.is_nan() && f64::from(narrowed) != value {
+                    return Err(overflow());
+                }
+                Variant::F32(narrowed)
+            }
+            VT_R8 => Variant::F64(float()?),
+            _ => return Err(mismatch()),
+        })
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl Variant {
+    /// Converts a JSON value into a variant of the requested type.
+    ///
+    /// JSON `null` always yields [`Variant::Null`]. With `VT_EMPTY` as
+    /// `target_type` the type is inferred from the JSON value; otherwise
+    /// numbers are coerced into the target type and scalars into strings
+    /// for `VT_BSTR`.
+    ///
+    /// # Errors
+    /// Returns `E_INVALIDARG` for arrays, objects, unsupported target types
+    /// and numbers that do not fit an integer target type, and
+    /// `DISP_E_OVERFLOW` for numbers outside the range of `VT_R4`.
+    pub fn from_json(value: &serde_json::Value, target_type: u16) -> windows::core::Result<Self> {
+        use serde_json::Value;
+        use windows::Win32::System::Variant::*;
+
+        fn integer<T: TryFrom<i64> + TryFrom<u64>>(
+            value: &serde_json::Number,
+        ) -> windows::core::Result<T> {
+            let converted = if let Some(value) = value.as_u64() {
+                T::try_from(value).ok()
+            } else if let Some(value) = value.as_i64() {
+                T::try_from(value).ok()
+            } else {
+                // Accept floats without a fractional part, such as `3.0`.
+                value
+                    .as_f64()
+                    .filter(|value| value.fract() == 0.0 && value.abs() < 9.2e18)
+                    .and_then(|value| T::try_from(value as i64).ok())
+            };
+
+            converted.ok_or_else(|| {
+                crate::error::invalid_arg(format!("{value} does not fit the target type"))
+            })
+        }
+
+        fn float(value: &serde_json::Number) -> windows::core::Result<f64> {
+            value
+                .as_f64()
+                .ok_or_else(|| crate::error::invalid_arg(format!("{value} is not a number")))
+        }
+
+        let number = match value {
+            Value::Null => return Ok(Variant::Null),
+            Value::Array(_) | Value::Object(_) => {
+                return Err(crate::error::invalid_arg(
+                    "JSON arrays and objects are not supported",
+                ));
+            }
+            Value::Bool(value) if matches!(VARENUM(target_type), VT_EMPTY | VT_BOOL) => {
+                return Ok(Variant::Bool(*value));
+            }
+            Value::String(value) if matches!(VARENUM(target_type), VT_EMPTY | VT_BSTR) => {
+                return Ok(Variant::String(value.clone()));
+            }
+            Value::Bool(_) | Value::String(_) if VARENUM(target_type) == VT_BSTR => {
+                return Ok(Variant::String(value.to_string()));
+            }
+            Value::Number(number) => number,
+            _ => {
+                return Err(crate::error::invalid_arg(format!(
+                    "Cannot convert {value} to VARTYPE {target_type}"
+                )));
+            }
+        };
+
+        Ok(match VARENUM(target_type) {
+            VT_EMPTY => {
+                if let Some(value) = number.as_i64() {
+                    Variant::I64(value)
+                } else if let Some(value) = number.as_u64() {
+                    Variant::U64(value)
+                } else {
+                    Variant::F64(number.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            VT_BSTR => Variant::String(number.to_string()),
+            VT_I1 => Variant::I8(integer(number)?),
+            VT_I2 => Variant::I16(integer(number)?),
+            VT_I4 => Variant::I32(integer(number)?),
+            VT_I8 => Variant::I64(integer(number)?),
+            VT_UI1 => Variant::U8(integer(number)?),
+            VT_UI2 => Variant::U16(integer(number)?),
+            VT_UI4 => Variant::U32(integer(number)?),
+            VT_UI8 => Variant::U64(integer(number)?),
+            VT_R4 => {
+                let value = float(number)?;
+                if value.abs() > f64::from(f32::MAX) {
+                    return Err(windows::core::Error::new(
+                        windows::Win32::Foundation::DISP_E_OVERFLOW,
+                        format!("{number} does not fit VT {target_type}"),
+                    ));
+                }
+                Variant::F32(value as f32)
+            }
+            VT_R8 => Variant::F64(float(number)?),
+            _ => {
+                return Err(crate::error::invalid_arg(format!(
+                    "Cannot convert {number} to VARTYPE {target_type}"
+                )));
+            }
+        })
+    }
+
+    /// Converts the variant into a JSON value.
+    ///
+    /// `Empty` and `Null` become JSON `null`, as do errors and non-finite
+    /// floats, which JSON cannot represent.
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value;
+
+        match self {
+            Variant::Empty | Variant::Null | Variant::Error(_) => Value::Null,
+            Variant::Bool(value) => Value::Bool(*value),
+            Variant::String(value) => Value::String(value.clone()),
+            Variant::I8(value) => Value::from(*value),
+            Variant::I16(value) => Value::from(*value),
+            Variant::I32(value) => Value::from(*value),
+            Variant::I64(value) => Value::from(*value),
+            Variant::F32(value) => {
+                serde_json::Number::from_f64(f64::from(*value)).map_or(Value::Null, Value::Number)
+            }
+            Variant::F64(value) => {
+                serde_json::Number::from_f64(*value).map_or(Value::Null, Value::Number)
+            }
+            Variant::U8(value) => Value::from(*value),
+            Variant::U16(value) => Value::from(*value),
+            Variant::U32(value) => Value::from(*value),
+            Variant::U64(value) => Value::from(*value),
+        }
+    }
+}
+
+impl PartialEq for Variant {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Variant::Empty, Variant::Empty) | (Variant::Null, Variant::Null) => true,
+            (Variant::Bool(a), Variant::Bool(b)) => a == b,
+            (Variant::String(a), Variant::String(b)) => a == b,
+            (Variant::I8(a), Variant::I8(b)) => a == b,
+            (Variant::I16(a), Variant::I16(b)) => a == b,
+            (Variant::I32(a), Variant::I32(b)) => a == b,
+            (Variant::I64(a), Variant::I64(b)) => a == b,
+            (Variant::F32(a), Variant::F32(b)) => a.to_bits() == b.to_bits(),
+            (Variant::F64(a), Variant::F64(b)) => a.to_bits() == b.to_bits(),
+            (Variant::U8(a), Variant::U8(b)) => a == b,
+            (Variant::U16(a), Variant::U16(b)) => a == b,
+            (Variant::U32(a), Variant::U32(b)) => a == b,
+            (Variant::U64(a), Variant::U64(b)) => a == b,
+            (Variant::Error(a), Variant::Error(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Variant {}
+
+impl core::hash::Hash for Variant {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            Variant::Empty | Variant::Null => {}
+            Variant::Bool(value) => value.hash(state),
+            Variant::String(value) => value.hash(state),
+            Variant::I8(value) => value.hash(state),
+            Variant::I16(value) => value.hash(state),
+            Variant::I32(value) => value.hash(state),
+            Variant::I64(value) => value.hash(state),
+            Variant::F32(value) => value.to_bits().hash(state),
+            Variant::F64(value) => value.to_bits().hash(state),
+            Variant::U8(value) => value.hash(state),
+            Variant::U16(value) => value.hash(state),
+            Variant::U32(value) => value.hash(state),
+            Variant::U64(value) => value.hash(state),
+            Variant::Error(value) => value.0.hash(state),
+        }
+    }
+}
+
+impl Variant {
+    /// Decodes a `VT_BYREF` value of type `vt` stored behind `pointer`.
+    ///
+    /// # Safety
+    /// `pointer` must be null or point to a valid value of type `vt`.
+    unsafe fn from_byref(
+        vt: windows::Win32::System::Variant::VARENUM,
+        pointer: *const core::ffi::c_void,
+    ) -> Self {
+        if pointer.is_null() {
+            return Variant::Empty;
+        }
+
+        unsafe {
+            match vt {
+                windows::Win32::System::Variant::VT_BOOL => Variant::Bool(
+                    (*pointer.cast::<windows::Win32::Foundation::VARIANT_BOOL>()).as_bool(),
+                ),
+                windows::Win32::System::Variant::VT_BSTR => {
+                    Variant::String((*pointer.cast::<windows::core::BSTR>()).to_string())
+                }
+                windows::Win32::System::Variant::VT_I1 => Variant::I8(*pointer.cast()),
+                windows::Win32::System::Variant::VT_I2 => Variant::I16(*pointer.cast()),
+                windows::Win32::System::Variant::VT_I4 => Variant::I32(*pointer.cast()),
+                windows::Win32::System::Variant::VT_I8 => Variant::I64(*pointer.cast()),
+                windows::Win32::System::Variant::VT_R4 => Variant::F32(*pointer.cast()),
+                windows::Win32::System::Variant::VT_R8 => Variant::F64(*pointer.cast()),
+                windows::Win32::System::Variant::VT_UI1 => Variant::U8(*pointer.cast()),
+                windows::Win32::System::Variant::VT_UI2 => Variant::U16(*pointer.cast()),
+                windows::Win32::System::Variant::VT_UI4 => Variant::U32(*pointer.cast()),
+                windows::Win32::System::Variant::VT_UI8 => Variant::U64(*pointer.cast()),
+                windows::Win32::System::Variant::VT_ERROR => {
+                    Variant::Error(windows::core::HRESULT(*pointer.cast()))
+                }
+                windows::Win32::System::Variant::VT_VARIANT => {
+                    Variant::from(&*pointer.cast::<windows::Win32::System::Variant::VARIANT>())
+                }
+                _ => Variant::Empty,
+            }
+        }
+    }
+}
+
+impl From<Variant> for windows::Win32::System::Variant::VARIANT {
+    fn from(val: Variant) -> Self {
+        match val {
+            Variant::Empty => Self::default(),
+            Variant::Null => {
+                let mut variant = Self::default();
+                unsafe {
+                    (*variant.Anonymous.Anonymous).vt = windows::Win32::System::Variant::VT_NULL
+                };
+                variant
+            }
+            Variant::Bool(value) => Self::from(value),
+            Variant::String(value) => Self::from(windows::core::BSTR::from(value)),
+            Variant::I8(value) => Self::from(value),
+            Variant::I16(value) => Self::from(value),
+            Variant::I32(value) => Self::from(value),
+            Variant::I64(value) => Self::from(value),
+            Variant::F32(value) => Self::from(value),
+            Variant::F64(value) => Self::from(value),
+            Variant::U8(value) => Self::from(value),
+            Variant::U16(value) => Self::from(value),
+            Variant::U32(value) => Self::from(value),
+            Variant::U64(value) => Self::from(value),
+            Variant::Error(value) => {
+                let mut variant = Self::default();
+                unsafe {
+                    (*variant.Anonymous.Anonymous).vt = windows::Win32::System::Variant::VT_ERROR;
+                    (*variant.Anonymous.Anonymous).Anonymous.scode = value.0;
+                };
+                variant
+            }
+        }
+    }
+}
+
+impl From<windows::Win32::System::Variant::VARIANT> for Variant {
+    fn from(value: windows::Win32::System::Variant::VARIANT) -> Self {
+        Variant::from(&value)
+    }
+}
+
+impl From<&windows::Win32::System::Variant::VARIANT> for Variant {
+    fn from(value: &windows::Win32::System::Variant::VARIANT) -> Self {
+        unsafe {
+            let value = &value.Anonymous.Anonymous;
+            if value.vt.0 & windows::Win32::System::Variant::VT_BYREF.0 != 0 {
+                return Variant::from_byref(
+                    windows::Win32::System::Variant::VARENUM(
+                        value.vt.0 & !windows::Win32::System::Variant::VT_BYREF.0,
+                    ),
+                    value.Anonymous.byref,
+                );
+            }
+
+            match value.vt {
+                windows::Win32::System::Variant::VT_EMPTY => Variant::Empty,
+                windows::Win32::System::Variant::VT_NULL => Variant::Null,
+                windows::Win32::System::Variant::VT_BOOL => {
+                    Variant::Bool(value.Anonymous.boolVal.as_bool())
+                }
+                windows::Win32::System::Variant::VT_BSTR => {
+                    Variant::String(value.Anonymous.bstrVal.to_string())
+                }
+                windows::Win32::System::Variant::VT_I1 => Variant::I8(value.Anonymous.cVal),
+                windows::Win32::System::Variant::VT_I2 => Variant::I16(value.Anonymous.iVal),
+                windows::Win32::System::Variant::VT_I4 => Variant::I32(value.Anonymous.lVal),
+                windows::Win32::System::Variant::VT_I8 => Variant::I64(value.Anonymous.llVal),
+                windows::Win32::System::Variant::VT_R4 => Variant::F32(value.Anonymous.fltVal),
+                windows::Win32::System::Variant::VT_R8 => Variant::F64(value.Anonymous.dblVal),
+                windows::Win32::System::Variant::VT_UI1 => Variant::U8(value.Anonymous.bVal),
+                windows::Win32::System::Variant::VT_UI2 => Variant::U16(value.Anonymous.uiVal),
+                windows::Win32::System::Variant::VT_UI4 => Variant::U32(value.Anonymous.ulVal),
+                windows::Win32::System::Variant::VT_UI8 => Variant::U64(value.Anonymous.ullVal),
+                windows::Win32::System::Variant::VT_ERROR => {
+                    Variant::Error(windows::core::HRESULT(value.Anonymous.scode))
+                }
+                _ => Variant::Empty,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,453 @@
+use windows::Win32::System::Variant::{VARENUM, VARIANT, VT_BYREF, VT_EMPTY, VT_I4, VT_VARIANT};
+
+use super::*;
+
+#[test]
+fn test_variant_from_byref_i4() {
+    let mut value = 42i32;
+    let mut variant = VARIANT::default();
+    unsafe {
+        let inner = &mut *variant.Anonymous.Anonymous;
+        inner.vt = VARENUM(VT_I4.0 | VT_BYREF.0);
+        inner.Anonymous.plVal = &mut value;
+    }
+
+    assert_eq!(Variant::from(&variant), Variant::I32(42));
+}
+
+#[test]
+fn test_variant_from_byref_variant() {
+    let mut value = VARIANT::from(7i32);
+    let mut variant = VARIANT::default();
+    unsafe {
+        let inner = &mut *variant.Anonymous.Anonymous;
+        inner.vt = VARENUM(VT_VARIANT.0 | VT_BYREF.0);
+        inner.Anonymous.pvarVal = &mut value;
+    }
+
+    assert_eq!(Variant::from(&variant), Variant::I32(7));
+}
+
+#[test]
+fn test_variant_from_null_byref() {
+    let mut variant = VARIANT::default();
+    unsafe {
+        (*variant.Anonymous.Anonymous).vt = VARENUM(VT_I4.0 | VT_BYREF.0);
+    }
+
+    assert_eq!(Variant::from(&variant), Variant::Empty);
+}
+
+#[test]
+fn test_property_id_round_trip() {
+    assert_eq!(PropertyId::from(103), PropertyId::EuLowLimit);
+    assert_eq!(u32::from(PropertyId::EuHighLimit), 102);
+    assert_eq!(PropertyId::from(5001), PropertyId::Vendor(5001));
+    assert_eq!(u32::from(PropertyId::Vendor(5001)), 5001);
+}
+
+#[test]
+fn test_server_status_bandwidth() {
+    let mut status = ServerStatus {
+        start_time: std::time::SystemTime::UNIX_EPOCH,
+        current_time: std::time::SystemTime::UNIX_EPOCH,
+        last_update_time: std::time::SystemTime::UNIX_EPOCH,
+        server_state: ServerState::Running,
+        group_count: 0,
+        band_width: ServerStatus::BANDWIDTH_UNKNOWN,
+        major_version: 1,
+        minor_version: 0,
+        build_number: 0,
+        vendor_info: String::new(),
+    };
+    assert_eq!(status.bandwidth(), None);
+    assert!(status.is_running());
+
+    status.band_width = 42;
+    status.server_state = ServerState::Suspended;
+    assert_eq!(status.bandwidth(), Some(42));
+    assert!(!status.is_running());
+}
+
+#[test]
+fn test_item_def_builder() {
+    let def = ItemDef::builder()
+        .item_id("Random.Int4")
+        .client_handle(7)
+        .blob(vec![1, 2, 3])
+        .build()
+        .unwrap();
+
+    assert_eq!(def.item_id, "Random.Int4");
+    assert!(def.active);
+    assert_eq!(def.data_type, VT_EMPTY.0);
+    assert_eq!(def.client_handle, 7);
+    assert_eq!(def.blob, vec![1, 2, 3]);
+
+    assert!(ItemDef::builder().build().is_err());
+}
+
+#[test]
+fn test_remote_variant_array_into_variants() {
+    let values = [
+        VARIANT::from(windows::core::BSTR::from("text")),
+        VARIANT::from(3i32),
+    ];
+    let pointer =
+        unsafe { windows::Win32::System::Com::CoTaskMemAlloc(core::mem::size_of_val(&values)) }
+            as *mut VARIANT;
+    assert!(!pointer.is_null());
+    unsafe { core::ptr::copy_nonoverlapping(values.as_ptr(), pointer, values.len()) };
+
+    let array = crate::utils::RemoteArray::from_mut_ptr(pointer, values.len() as u32);
+    assert_eq!(
+        array.into_variants(),
+        vec![Variant::String("text".to_string()), Variant::I32(3)]
+    );
+}
+
+#[test]
+fn test_read_results() {
+    let value = |v: i32| ItemValue {
+        value: VARIANT::from(v),
+        quality: 0xC0,
+        timestamp: std::time::UNIX_EPOCH,
+    };
+    let results = ReadResults(vec![
+        Ok(value(1)),
+        Err(windows::Win32::Foundation::E_FAIL.into()),
+        Ok(value(3)),
+    ]);
+
+    assert!(!results.all_ok());
+    assert_eq!(results.oks().count(), 2);
+    assert_eq!(
+        results
+            .errors()
+            .map(|(index, error)| (index, error.code()))
+            .collect::<Vec<_>>(),
+        vec![(1, windows::Win32::Foundation::E_FAIL)]
+    );
+
+    let (values, errors) = results.into_map(&["a", "b", "c"]).unwrap();
+    assert_eq!(Variant::from(&values["c"].value), Variant::I32(3));
+    assert_eq!(values.len(), 2);
+    assert_eq!(errors[0].0, "b");
+
+    assert!(ReadResults(vec![]).into_map(&["a"]).is_err());
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_subscription_spec_serde_round_trip() {
+    let spec = SubscriptionSpec {
+        state: GroupState {
+            name: "Group".to_string(),
+            update_rate: 250,
+            ..Default::default()
+        },
+        items: vec![ItemDef::builder().item_id("Tag1").build().unwrap()],
+    };
+
+    let json = serde_json::to_string(&spec).unwrap();
+    let restored: SubscriptionSpec = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, spec);
+}
+
+#[test]
+fn test_variant_coerce_to() {
+    use windows::Win32::{
+        Foundation::{DISP_E_OVERFLOW, DISP_E_TYPEMISMATCH},
+        System::Variant::{VT_BSTR, VT_I2, VT_R4, VT_UI1},
+    };
+
+    assert_eq!(
+        Variant::I32(5).coerce_to(VT_R4.0).unwrap(),
+        Variant::F32(5.0)
+    );
+    assert_eq!(
+        Variant::F64(-3.0).coerce_to(VT_I2.0).unwrap(),
+        Variant::I16(-3)
+    );
+    assert_eq!(
+        Variant::I32(7).coerce_to(VT_EMPTY.0).unwrap(),
+        Variant::I32(7)
+    );
+    assert_eq!(Variant::Null.coerce_to(VT_I4.0).unwrap(), Variant::Null);
+
+    let error_code =
+        |variant: Variant, data_type: u16| variant.coerce_to(data_type).unwrap_err().code();
+    assert_eq!(error_code(Variant::F64(40000.0), VT_I2.0), DISP_E_OVERFLOW);
+    assert_eq!(error_code(Variant::F64(1.5), VT_I4.0), DISP_E_OVERFLOW);
+    assert_eq!(error_code(Variant::I32(-1), VT_UI1.0), DISP_E_OVERFLOW);
+    assert_eq!(
+        error_code(Variant::I32(16_777_217), VT_R4.0),
+        DISP_E_OVERFLOW
+    );
+    assert_eq!(error_code(Variant::F64(0.1), VT_R4.0), DISP_E_OVERFLOW);
+    assert_eq!(error_code(Variant::I32(1), VT_BSTR.0), DISP_E_TYPEMISMATCH);
+    assert_eq!(
+        error_code(Variant::String("1".to_string()), VT_I4.0),
+        DISP_E_TYPEMISMATCH
+    );
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_variant_from_json() {
+    use windows::Win32::{
+        Foundation::{DISP_E_OVERFLOW, E_INVALIDARG},
+        System::Variant::{VT_I2, VT_R4, VT_R8},
+    };
+
+    let from_json = |json: &str, data_type: u16| {
+        Variant::from_json(&serde_json::from_str(json).unwrap(), data_type)
+    };
+    assert_eq!(from_json("0.5", VT_R4.0).unwrap(), Variant::F32(0.5));
+    assert_eq!(from_json("-2", VT_R8.0).unwrap(), Variant::F64(-2.0));
+    assert_eq!(
+        from_json("1e39", VT_R4.0).unwrap_err().code(),
+        DISP_E_OVERFLOW
+    );
+    assert_eq!(
+        from_json("-1e39", VT_R4.0).unwrap_err().code(),
+        DISP_E_OVERFLOW
+    );
+    assert_eq!(
+        from_json("40000", VT_I2.0).unwrap_err().code(),
+        E_INVALIDARG
+    );
+    assert_eq!(from_json("[1]", VT_R4.0).unwrap_err().code(), E_INVALIDARG);
+}
+
+#[test]
+fn test_data_source_target_max_ages() {
+    assert_eq!(
+        DataSourceTarget::ForceCache.to_max_ages(2),
+        vec![u32::MAX; 2]
+    );
+    assert_eq!(DataSourceTarget::ForceDevice.to_max_ages(1), vec![0]);
+    assert_eq!(
+        DataSourceTarget::WithMaxAge(500).to_max_ages(0),
+        Vec::<u32>::new()
+    );
+
+    assert_eq!(
+        DataSourceTarget::ForceCache.try_to_native().unwrap(),
+        opc_da_bindings::OPC_DS_CACHE
+    );
+    assert_eq!(
+        DataSourceTarget::WithMaxAge(0).try_to_native().unwrap(),
+        opc_da_bindings::OPC_DS_DEVICE
+    );
+    assert!(DataSourceTarget::WithMaxAge(500).try_to_native().is_err());
+}
+
+#[test]
+fn test_vqt_from_properties() {
+    use windows::Win32::System::Variant::{VT_DATE, VT_FILETIME, VT_UI2};
+
+    let with = |vt: VARENUM, set: &dyn Fn(&mut VARIANT)| {
+        let mut variant = VARIANT::default();
+        unsafe { (*variant.Anonymous.Anonymous).vt = vt };
+        set(&mut variant);
+        variant
+    };
+    let value = VARIANT::from(42i32);
+    let quality = with(VT_UI2, &|v| unsafe {
+        (*v.Anonymous.Anonymous).Anonymous.uiVal = 0xC0
+    });
+    let expected = std::time::UNIX_EPOCH + std::time::Duration::from_secs(86400);
+
+    // 1970-01-02 as an OLE date.
+    let date = with(VT_DATE, &|v| unsafe {
+        (*v.Anonymous.Anonymous).Anonymous.date = 25570.0
+    });
+    let vqt = Vqt::from_properties(&value, &quality, &date).unwrap();
+    assert_eq!(vqt.value, Variant::I32(42));
+    assert_eq!(vqt.quality, 0xC0);
+    assert_eq!(vqt.timestamp, expected);
+
+    // 1970-01-02 as a FILETIME.
+    let filetime = with(VT_FILETIME, &|v| unsafe {
+        (*v.Anonymous.Anonymous).Anonymous.ullVal = (11_644_473_600 + 86400) * 10_000_000
+    });
+    let vqt = Vqt::from_properties(&value, &quality, &filetime).unwrap();
+    assert_eq!(vqt.timestamp, expected);
+
+    let float = VARIANT::from(1.5f64);
+    assert!(Vqt::from_properties(&value, &float, &date).is_err());
+    assert!(Vqt::from_properties(&value, &quality, &value).is_err());
+}
+
+#[test]
+fn test_local_pointer_pcwstr_slice() {
+    let names = ["Tag.Speed".to_string(), "Tag.Running".to_string()];
+    let pointer = crate::utils::LocalPointer::from(&names[..]);
+
+    let slice = pointer.as_pcwstr_slice();
+    assert_eq!(slice, pointer.pcwstr_iter().collect::<Vec<_>>());
+    assert_eq!(unsafe { slice[1].to_string() }.unwrap(), "Tag.Running");
+    // The array is cached.
+    assert_eq!(pointer.as_pcwstr_slice().as_ptr(), slice.as_ptr());
+
+    let moved = pointer;
+    assert_eq!(
+        unsafe { moved.as_pcwstr_slice()[0].to_string() }.unwrap(),
+        "Tag.Speed"
+    );
+
+    let null = crate::utils::LocalPointer::<Vec<Vec<u16>>>::new(None);
+    assert_eq!(null.as_pcwstr_slice(), [windows::core::PCWSTR::null()]);
+}
+
+#[test]
+fn test_string_write_cache() {
+    use crate::utils::StringWriteCache;
+
+    let mut cache = StringWriteCache::new();
+    let running = Variant::String("RUNNING".to_string());
+
+    let first = cache.to_variant(&running);
+    let second = cache.to_variant(&running);
+    let number = cache.to_variant(&Variant::I32(1));
+    assert_eq!(cache.len(), 1);
+
+    assert_eq!(Variant::from(&first), running);
+    assert_eq!(Variant::from(&second), running);
+    assert_eq!(Variant::from(&number), Variant::I32(1));
+    // Every VARIANT owns a distinct BSTR.
+    assert_ne!(
+        unsafe { first.Anonymous.Anonymous.Anonymous.bstrVal.as_ptr() },
+        unsafe { second.Anonymous.Anonymous.Anonymous.bstrVal.as_ptr() }
+    );
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+/// Counts the heap allocations made by the current thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+}
+
+unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        unsafe { std::alloc::System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        unsafe { std::alloc::System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn count_allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = ALLOCATIONS.with(core::cell::Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(core::cell::Cell::get) - before)
+}
+
+#[test]
+fn test_string_write_cache_allocations() {
+    use crate::utils::StringWriteCache;
+
+    let mut cache = StringWriteCache::new();
+    let running = Variant::String("RUNNING".to_string());
+
+    // Uncached, the string is encoded into a heap buffer before the `BSTR`
+    // (allocated by `SysAllocStringLen`, not counted) copies it.
+    let (uncached, allocations) =
+        count_allocations(|| VARIANT::from(windows::core::BSTR::from("RUNNING")));
+    assert!(allocations >= 1, "{allocations} allocations");
+    let (_, allocations) = count_allocations(|| cache.to_variant(&running));
+    assert!(allocations >= 1, "{allocations} allocations");
+
+    // Cached, the `BSTR` is the only allocation left.
+    let (cached, allocations) = count_allocations(|| cache.to_variant(&running));
+    assert_eq!(allocations, 0);
+    assert_eq!(Variant::from(&cached), Variant::from(&uncached));
+}
+
+#[test]
+fn test_variant_error_round_trip() {
+    use windows::Win32::System::Variant::VT_ERROR;
+
+    let error = Variant::Error(windows::Win32::Foundation::E_FAIL);
+    let variant = VARIANT::from(error.clone());
+    assert_eq!(variant.vt(), VT_ERROR);
+    assert_eq!(
+        unsafe { variant.Anonymous.Anonymous.Anonymous.scode },
+        windows::Win32::Foundation::E_FAIL.0
+    );
+    assert_eq!(Variant::from(&variant), error);
+    assert_eq!(error.get_data_type(), VT_ERROR.0);
+    assert_ne!(Variant::from(&variant), Variant::Empty);
+}
+
+#[test]
+fn test_remote_hresult_array_into_results() {
+    let errors = [
+        windows::Win32::Foundation::S_OK,
+        windows::Win32::Foundation::S_FALSE,
+        windows::Win32::Foundation::E_FAIL,
+    ];
+    let pointer =
+        unsafe { windows::Win32::System::Com::CoTaskMemAlloc(core::mem::size_of_val(&errors)) }
+            as *mut windows::core::HRESULT;
+    assert!(!pointer.is_null());
+    unsafe { core::ptr::copy_nonoverlapping(errors.as_ptr(), pointer, errors.len()) };
+
+    let array = crate::utils::RemoteArray::from_mut_ptr(pointer, errors.len() as u32);
+    let results = array.into_results();
+    assert!(results[0].is_ok() && results[1].is_ok());
+    assert_eq!(
+        results[2].as_ref().unwrap_err().code(),
+        windows::Win32::Foundation::E_FAIL
+    );
+    assert!(!array.all_succeeded());
+    assert!(crate::utils::RemoteArray::<windows::core::HRESULT>::empty().all_succeeded());
+}
+
+#[test]
+fn test_eu_info_from_native() {
+    use windows::Win32::System::{
+        Ole::{SafeArrayCreateVector, SafeArrayPutElement},
+        Variant::{VT_ARRAY, VT_R8},
+    };
+
+    let limits = [0.0f64, 100.0];
+    let array = unsafe { SafeArrayCreateVector(VT_R8, 0, limits.len() as u32) };
+    assert!(!array.is_null());
+    for (index, limit) in limits.iter().enumerate() {
+        unsafe {
+            SafeArrayPutElement(array, &(index as i32), (limit as *const f64).cast()).unwrap();
+        }
+    }
+
+    let mut variant = VARIANT::default();
+    unsafe {
+        (*variant.Anonymous.Anonymous).vt = VARENUM(VT_ARRAY.0 | VT_R8.0);
+        (*variant.Anonymous.Anonymous).Anonymous.parray = array;
+    }
+
+    assert_eq!(
+        EuInfo::from_native(&EuType::Analog, &variant).unwrap(),
+        Some(EuInfo::Analog {
+            low: 0.0,
+            high: 100.0,
+            units: None
+        })
+    );
+    assert!(EuInfo::from_native(&EuType::Enumerated, &variant).is_err());
+    assert_eq!(
+        EuInfo::from_native(&EuType::NoEnum, &variant).unwrap(),
+        None
+    );
+    // Dropping the VARIANT destroys the array.
+}
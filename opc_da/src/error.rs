@@ -0,0 +1,169 @@
+//! Helpers for building `windows::core::Error` values.
+//!
+//! Each helper pairs a descriptive message with the HRESULT that matches the
+//! failure, so call sites stay short and report consistent codes.
+
+/// Creates an `E_INVALIDARG` error, for arguments rejected by the caller or the server.
+pub fn invalid_arg<T: AsRef<str>>(message: T) -> windows::core::Error {
+    windows::core::Error::new(windows::Win32::Foundation::E_INVALIDARG, message)
+}
+
+/// Creates an `E_NOTIMPL` error, for operations unsupported by a server version.
+pub fn not_impl<T: AsRef<str>>(message: T) -> windows::core::Error {
+    windows::core::Error::new(windows::Win32::Foundation::E_NOTIMPL, message)
+}
+
+/// Creates an `E_POINTER` error, for null pointers where a value is required.
+pub fn pointer<T: AsRef<str>>(message: T) -> windows::core::Error {
+    windows::core::Error::new(windows::Win32::Foundation::E_POINTER, message)
+}
+
+/// Creates an `E_FAIL` error, for failures without a more specific code.
+pub fn fail<T: AsRef<str>>(message: T) -> windows::core::Error {
+    windows::core::Error::new(windows::Win32::Foundation::E_FAIL, message)
+}
+
+/// Creates an error from the calling thread's last Win32 error.
+///
+/// The system message is prefixed with `context`, e.g. `"CoTaskMemAlloc: Not enough memory"`.
+pub fn from_win32_context<T: AsRef<str>>(context: T) -> windows::core::Error {
+    let error = windows::core::Error::from_win32();
+    windows::core::Error::new(
+        error.code(),
+        format!("{}: {}", context.as_ref(), error.message()),
+    )
+}
+
+/// Returns the symbolic name of an OPC or common COM HRESULT.
+///
+/// The OPC codes from `opcerror.h` are not in the `windows` crate, so a
+/// failure such as `0xC0040007` can be reported as `OPC_E_UNKNOWNITEMID`.
+pub fn hresult_name(code: windows::core::HRESULT) -> Option<&'static str> {
+    Some(match code.0 as u32 {
+        // OPC DA, opcerror.h.
+        0xC0040001 => "OPC_E_INVALIDHANDLE",
+        0xC0040004 => "OPC_E_BADTYPE",
+        0xC0040005 => "OPC_E_PUBLIC",
+        0xC0040006 => "OPC_E_BADRIGHTS",
+        0xC0040007 => "OPC_E_UNKNOWNITEMID",
+        0xC0040008 => "OPC_E_INVALIDITEMID",
+        0xC0040009 => "OPC_E_INVALIDFILTER",
+        0xC004000A => "OPC_E_UNKNOWNPATH",
+        0xC004000B => "OPC_E_RANGE",
+        0xC004000C => "OPC_E_DUPLICATENAME",
+        0x0004000D => "OPC_S_UNSUPPORTEDRATE",
+        0x0004000E => "OPC_S_CLAMP",
+        0x0004000F => "OPC_S_INUSE",
+        0xC0040010 => "OPC_E_INVALIDCONFIGFILE",
+        0xC0040011 => "OPC_E_NOTFOUND",
+        0xC0040203 => "OPC_E_INVALID_PID",
+        0xC0040400 => "OPC_E_DEADBANDNOTSET",
+        0xC0040401 => "OPC_E_DEADBANDNOTSUPPORTED",
+        0xC0040402 => "OPC_E_NOBUFFERING",
+        0xC0040403 => "OPC_E_INVALIDCONTINUATIONPOINT",
+        0x00040404 => "OPC_S_DATAQUEUEOVERFLOW",
+        0xC0040405 => "OPC_E_RATENOTSET",
+        0xC0040406 => "OPC_E_NOTSUPPORTED",
+        // COM.
+        0x00000000 => "S_OK",
+        0x00000001 => "S_FALSE",
+        0x80004001 => "E_NOTIMPL",
+        0x80004002 => "E_NOINTERFACE",
+        0x80004003 => "E_POINTER",
+        0x80004004 => "E_ABORT",
+        0x80004005 => "E_FAIL",
+        0x8000FFFF => "E_UNEXPECTED",
+        0x80070005 => "E_ACCESSDENIED",
+        0x80070006 => "E_HANDLE",
+        0x8007000E => "E_OUTOFMEMORY",
+        0x80070057 => "E_INVALIDARG",
+        0x80020005 => "DISP_E_TYPEMISMATCH",
+        0x80020008 => "DISP_E_BADVARTYPE",
+        0x8002000A => "DISP_E_OVERFLOW",
+        0x80040154 => "REGDB_E_CLASSNOTREG",
+        0x80040200 => "CONNECT_E_NOCONNECTION",
+        0x80040201 => "CONNECT_E_ADVISELIMIT",
+        0x800401F0 => "CO_E_NOTINITIALIZED",
+        0x800401F3 => "CO_E_CLASSSTRING",
+        0x80010001 => "RPC_E_CALL_REJECTED",
+        0x80010105 => "RPC_E_SERVERFAULT",
+        0x80010108 => "RPC_E_DISCONNECTED",
+        0x8001010E => "RPC_E_WRONG_THREAD",
+        0x8001011F => "RPC_E_TIMEOUT",
+        0x800706BA => "RPC_S_SERVER_UNAVAILABLE",
+        0x800706BE => "RPC_S_CALL_FAILED",
+        _ => return None,
+    })
+}
+
+/// A `windows::core::Error` with a readable description.
+///
+/// `Display` prints the description and the HRESULT, with its name when
+/// [`hresult_name`] knows it. The description is the server's
+/// `GetErrorString` text when built with `Server::describe_error` of the
+/// unified client, otherwise the system message.
+#[derive(Debug, Clone)]
+pub struct OpcError {
+    error: windows::core::Error,
+    message: String,
+}
+
+impl OpcError {
+    /// Wraps `error` with `message`, the system message is used if it is empty.
+    pub fn new<T: Into<String>>(error: windows::core::Error, message: T) -> Self {
+        let message = message.into();
+        let message = if message.trim().is_empty() {
+            error.message()
+        } else {
+            message
+        };
+
+        Self { error, message }
+    }
+
+    /// Returns the HRESULT of the error.
+    pub fn code(&self) -> windows::core::HRESULT {
+        self.error.code()
+    }
+
+    /// Returns the description printed by `Display`.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the wrapped error.
+    pub fn into_inner(self) -> windows::core::Error {
+        self.error
+    }
+}
+
+impl From<windows::core::Error> for OpcError {
+    fn from(error: windows::core::Error) -> Self {
+        Self::new(error, String::new())
+    }
+}
+
+impl From<OpcError> for windows::core::Error {
+    fn from(error: OpcError) -> Self {
+        error.error
+    }
+}
+
+impl core::fmt::Display for OpcError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let message = self.message.trim_end();
+        let code = self.code();
+        match (message.is_empty(), hresult_name(code)) {
+            (true, Some(name)) => write!(f, "{name} (0x{:08X})", code.0),
+            (true, None) => write!(f, "HRESULT 0x{:08X}", code.0),
+            (false, Some(name)) => write!(f, "{message} ({name}, 0x{:08X})", code.0),
+            (false, None) => write!(f, "{message} (0x{:08X})", code.0),
+        }
+    }
+}
+
+impl std::error::Error for OpcError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
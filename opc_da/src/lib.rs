@@ -1,4 +1,5 @@
 pub mod def;
+pub mod error;
 pub mod utils;
 
 #[cfg(feature = "unstable_client")]
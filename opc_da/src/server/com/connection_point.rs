@@ -4,6 +4,8 @@ use windows::Win32::System::Com::{
     IConnectionPoint, IConnectionPointContainer, IConnectionPoint_Impl, IEnumConnections,
 };
 
+use crate::error;
+
 use super::enumeration::ConnectionsEnumerator;
 
 #[windows::core::implement(IConnectionPoint)]
@@ -11,7 +13,7 @@ pub struct ConnectionPoint {
     container: IConnectionPointContainer,
     interface_id: windows::core::GUID,
     next_cookie: core::sync::atomic::AtomicU32,
-    connections: tokio::sync::RwLock<BTreeMap<u32, windows::core::IUnknown>>,
+    connections: std::sync::RwLock<BTreeMap<u32, windows::core::IUnknown>>,
 }
 
 impl ConnectionPoint {
@@ -23,7 +25,7 @@ impl ConnectionPoint {
             container,
             interface_id,
             next_cookie: core::sync::atomic::AtomicU32::new(0),
-            connections: tokio::sync::RwLock::new(BTreeMap::new()),
+            connections: std::sync::RwLock::new(BTreeMap::new()),
         }
     }
 }
@@ -44,22 +46,19 @@ impl IConnectionPoint_Impl for ConnectionPoint_Impl {
         let cookie = self
             .next_cookie
             .fetch_add(1, core::sync::atomic::Ordering::SeqCst);
-        self.connections
-            .blocking_write()
-            .insert(cookie, sink.unwrap().clone());
+        write(&self.connections).insert(cookie, sink.unwrap().clone());
         Ok(cookie)
     }
 
     fn Unadvise(&self, cookie: u32) -> windows::core::Result<()> {
-        self.connections.blocking_write().remove(&cookie);
+        write(&self.connections).remove(&cookie);
         Ok(())
     }
 
     fn EnumConnections(&self) -> windows::core::Result<IEnumConnections> {
         Ok(
             windows::core::ComObjectInner::into_object(ConnectionsEnumerator::new(
-                self.connections
-                    .blocking_read()
+                read(&self.connections)
                     .iter()
                     .map(|(k, v)| windows::Win32::System::Com::CONNECTDATA {
                         pUnk: ManuallyDrop::new(Some(v.clone())),
@@ -71,3 +70,169 @@ impl IConnectionPoint_Impl for ConnectionPoint_Impl {
         )
     }
 }
+
+/// Connection point for `IOPCDataCallback` sinks, owned by its group.
+///
+/// The container is held weakly, as the group owns the connection point.
+#[windows::core::implement(IConnectionPoint)]
+pub struct DataCallbackConnectionPoint {
+    container: windows::core::Weak<IConnectionPointContainer>,
+    next_cookie: core::sync::atomic::AtomicU32,
+    sinks: std::sync::RwLock<BTreeMap<u32, opc_da_bindings::IOPCDataCallback>>,
+}
+
+impl DataCallbackConnectionPoint {
+    /// Creates a connection point without keeping `container` alive.
+    ///
+    /// # Errors
+    /// Returns the error of `IWeakReferenceSource` if `container` does not
+    /// support weak references.
+    pub fn new(
+        container: &IConnectionPointContainer,
+    ) -> windows::core::Result<DataCallbackConnectionPoint> {
+        Ok(DataCallbackConnectionPoint {
+            container: windows::core::Interface::downgrade(container)?,
+            next_cookie: core::sync::atomic::AtomicU32::new(0),
+            sinks: std::sync::RwLock::new(BTreeMap::new()),
+        })
+    }
+
+    /// Returns `true` if at least one `IOPCDataCallback` sink is advised.
+    pub fn has_sinks(&self) -> bool {
+        !read(&self.sinks).is_empty()
+    }
+
+    /// Invokes `IOPCDataCallback::OnDataChange` on every advised sink.
+    ///
+    /// # Arguments
+    /// * `transaction_id` - Transaction id, `0` for subscription updates
+    /// * `group_client_handle` - Client handle of the group
+    /// * `master_quality` - `S_OK` if all qualities are good, `S_FALSE` otherwise
+    /// * `master_error` - `S_OK` if all errors are `S_OK`, `S_FALSE` otherwise
+    /// * `client_handles` - Client handles of the changed items
+    /// * `values` - Values of the changed items
+    /// * `qualities` - Qualities of the changed items
+    /// * `timestamps` - Timestamps of the changed items
+    /// * `errors` - Per-item error codes
+    ///
+    /// # Errors
+    /// Returns `E_INVALIDARG` if the slices differ in length. Every sink is
+    /// called even if one fails; the first sink error is returned.
+    #[allow(clippy::too_many_arguments)]
+    pub fn broadcast_data_change(
+        &self,
+        transaction_id: u32,
+        group_client_handle: u32,
+        master_quality: windows::core::HRESULT,
+        master_error: windows::core::HRESULT,
+        client_handles: &[u32],
+        values: &[windows::Win32::System::Variant::VARIANT],
+        qualities: &[u16],
+        timestamps: &[windows::Win32::Foundation::FILETIME],
+        errors: &[windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        let count = client_handles.len();
+        if values.len() != count
+            || qualities.len() != count
+            || timestamps.len() != count
+            || errors.len() != count
+        {
+            return Err(error::invalid_arg(
+                "Data change arrays must have the same length",
+            ));
+        }
+
+        // Clone the sinks so that a callback which advises or unadvises
+        // does not dead-lock on the map.
+        let sinks: Vec<_> = read(&self.sinks).values().cloned().collect();
+
+        let mut result = Ok(());
+        for sink in sinks {
+            let call = unsafe {
+                sink.OnDataChange(
+                    transaction_id,
+                    group_client_handle,
+                    master_quality,
+                    master_error,
+                    count as u32,
+                    client_handles.as_ptr(),
+                    values.as_ptr(),
+                    qualities.as_ptr(),
+                    timestamps.as_ptr(),
+                    errors.as_ptr(),
+                )
+            };
+
+            if result.is_ok() {
+                result = call;
+            }
+        }
+
+        result
+    }
+}
+
+impl IConnectionPoint_Impl for DataCallbackConnectionPoint_Impl {
+    fn GetConnectionInterface(&self) -> windows::core::Result<windows::core::GUID> {
+        Ok(<opc_da_bindings::IOPCDataCallback as windows::core::Interface>::IID)
+    }
+
+    fn GetConnectionPointContainer(&self) -> windows::core::Result<IConnectionPointContainer> {
+        self.container
+            .upgrade()
+            .ok_or_else(|| error::fail("Connection point container was released"))
+    }
+
+    fn Advise(
+        &self,
+        sink: windows::core::Ref<'_, windows::core::IUnknown>,
+    ) -> windows::core::Result<u32> {
+        let sink = sink
+            .ok()
+            .map_err(|_| windows::core::Error::from(windows::Win32::Foundation::E_POINTER))?;
+        let callback: opc_da_bindings::IOPCDataCallback = windows::core::Interface::cast(sink)
+            .map_err(|_| {
+                windows::core::Error::new(
+                    windows::Win32::System::Ole::CONNECT_E_CANNOTCONNECT,
+                    "Sink does not implement IOPCDataCallback",
+                )
+            })?;
+
+        let cookie = self
+            .next_cookie
+            .fetch_add(1, core::sync::atomic::Ordering::SeqCst);
+        write(&self.sinks).insert(cookie, callback);
+        Ok(cookie)
+    }
+
+    fn Unadvise(&self, cookie: u32) -> windows::core::Result<()> {
+        match write(&self.sinks).remove(&cookie) {
+            Some(_) => Ok(()),
+            None => Err(windows::Win32::System::Ole::CONNECT_E_NOCONNECTION.into()),
+        }
+    }
+
+    fn EnumConnections(&self) -> windows::core::Result<IEnumConnections> {
+        Ok(
+            windows::core::ComObjectInner::into_object(ConnectionsEnumerator::new(
+                read(&self.sinks)
+                    .iter()
+                    .map(|(k, v)| windows::Win32::System::Com::CONNECTDATA {
+                        pUnk: ManuallyDrop::new(Some(v.clone().into())),
+                        dwCookie: *k,
+                    })
+                    .collect(),
+            ))
+            .into_interface(),
+        )
+    }
+}
+
+fn read<T>(lock: &std::sync::RwLock<T>) -> std::sync::RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn write<T>(lock: &std::sync::RwLock<T>) -> std::sync::RwLockWriteGuard<'_, T> {
+    lock.write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
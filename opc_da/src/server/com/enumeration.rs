@@ -1,19 +1,23 @@
 use windows::{
     Win32::{
-        Foundation::{S_FALSE, S_OK},
+        Foundation::{E_OUTOFMEMORY, S_FALSE, S_OK},
         System::Com::{
-            CONNECTDATA, IConnectionPoint, IEnumConnectionPoints, IEnumConnectionPoints_Impl,
-            IEnumConnections, IEnumConnections_Impl, IEnumString, IEnumString_Impl, IEnumUnknown,
-            IEnumUnknown_Impl,
+            CONNECTDATA, CoTaskMemAlloc, CoTaskMemFree, IConnectionPoint, IEnumConnectionPoints,
+            IEnumConnectionPoints_Impl, IEnumConnections, IEnumConnections_Impl, IEnumString,
+            IEnumString_Impl, IEnumUnknown, IEnumUnknown_Impl,
         },
     },
     core::PWSTR,
 };
 
-use crate::safe_call;
+use crate::{def::ItemAttributes, utils::ToNative as _};
 
-use super::memory::{FreeRaw as _, IntoArrayRef, IntoComArrayRef, IntoRef as _};
+use super::memory::{IntoArrayRef, IntoComArrayRef, IntoRef as _};
 
+/// Shared state of the COM enumerators.
+///
+/// The cursor is an atomic rather than a lock, so the vtable methods never
+/// block and are safe to call from any thread, including async runtime workers.
 struct Enumerator<T> {
     items: Vec<T>,
     index: core::sync::atomic::AtomicUsize,
@@ -27,44 +31,66 @@ impl<T: Clone> Enumerator<T> {
         }
     }
 
-    pub fn next(
+    /// Atomically reserves up to `count` items starting at the cursor.
+    ///
+    /// The cursor is advanced in a single compare-and-swap and clamped to the
+    /// number of items, so concurrent callers receive disjoint ranges and the
+    /// cursor can never overflow.
+    fn reserve(&self, count: u32) -> &[T] {
+        let len = self.items.len();
+        let advance = |index: usize| index.saturating_add(count as usize).min(len);
+        let start = match self.index.fetch_update(
+            core::sync::atomic::Ordering::SeqCst,
+            core::sync::atomic::Ordering::SeqCst,
+            |index| Some(advance(index)),
+        ) {
+            Ok(index) | Err(index) => index.min(len),
+        };
+
+        &self.items[start..advance(start)]
+    }
+
+    /// Writes the next `count` items into `elements`, converted by `map`.
+    ///
+    /// `elements` is treated as uninitialized output memory, so no previous
+    /// values are dropped.
+    fn next_with<U>(
         &self,
         count: u32,
         fetched: &mut u32,
-        elements: &mut [T],
+        elements: &mut [U],
+        map: impl Fn(&T) -> U,
     ) -> windows::core::HRESULT {
-        let current_index = self
-            .index
-            .fetch_add(count as _, core::sync::atomic::Ordering::SeqCst);
-        if current_index >= self.items.len() {
-            return S_FALSE;
+        let items = self.reserve(count);
+
+        for (element, item) in elements.iter_mut().zip(items) {
+            unsafe { core::ptr::write(element, map(item)) };
         }
 
-        let end_index = (current_index + count as usize).min(self.items.len());
-        let slice = &self.items[current_index..end_index];
-        *fetched = slice.len() as u32;
+        *fetched = items.len() as u32;
 
-        for (i, element) in slice.iter().enumerate() {
-            elements[i] = element.clone();
+        if items.len() == count as usize {
+            S_OK
+        } else {
+            S_FALSE
         }
+    }
 
-        S_OK
+    pub fn next(
+        &self,
+        count: u32,
+        fetched: &mut u32,
+        elements: &mut [T],
+    ) -> windows::core::HRESULT {
+        self.next_with(count, fetched, elements, T::clone)
     }
 
     pub fn skip(&self, count: u32) -> windows::core::HRESULT {
-        let current_index = self.index.load(core::sync::atomic::Ordering::SeqCst);
-        let new_index = current_index.saturating_add(count as usize);
-        let max_index = self.items.len();
-
-        if new_index >= max_index {
-            self.index
-                .store(max_index, core::sync::atomic::Ordering::SeqCst);
+        if self.reserve(count).len() == count as usize {
+            S_OK
         } else {
-            self.index
-                .store(new_index, core::sync::atomic::Ordering::SeqCst);
+            S_FALSE
         }
-
-        S_OK
     }
 
     fn reset(&self) -> windows::core::HRESULT {
@@ -102,7 +128,7 @@ pub struct ConnectionsEnumerator(Enumerator<CONNECTDATA>);
 
 #[windows::core::implement(opc_da_bindings::IEnumOPCItemAttributes)]
 #[repr(transparent)]
-pub struct ItemAttributesEnumerator(Enumerator<opc_da_bindings::tagOPCITEMATTRIBUTES>);
+pub struct ItemAttributesEnumerator(Enumerator<ItemAttributes>);
 
 impl StringEnumerator {
     pub fn new(strings: Vec<String>) -> Self {
@@ -134,11 +160,72 @@ impl ConnectionsEnumerator {
 }
 
 impl ItemAttributesEnumerator {
-    pub fn new(items: Vec<opc_da_bindings::tagOPCITEMATTRIBUTES>) -> Self {
+    pub fn new(items: Vec<ItemAttributes>) -> Self {
         Self(Enumerator::new(items))
     }
 }
 
+/// Copies `values` into a new `CoTaskMemAlloc` block, null when empty.
+fn co_task_mem_copy<T: Copy>(values: &[T]) -> Option<*mut T> {
+    if values.is_empty() {
+        return Some(core::ptr::null_mut());
+    }
+
+    let pointer = unsafe { CoTaskMemAlloc(core::mem::size_of_val(values)) }.cast::<T>();
+    if pointer.is_null() {
+        return None;
+    }
+
+    unsafe { core::ptr::copy_nonoverlapping(values.as_ptr(), pointer, values.len()) };
+    Some(pointer)
+}
+
+/// Frees the memory owned by an item attributes copy handed out by `Next`.
+fn free_item_attributes(attributes: opc_da_bindings::tagOPCITEMATTRIBUTES) {
+    unsafe {
+        CoTaskMemFree(Some(attributes.szAccessPath.0.cast()));
+        CoTaskMemFree(Some(attributes.szItemID.0.cast()));
+        CoTaskMemFree(Some(attributes.pBlob.cast()));
+    }
+}
+
+/// Deep copies item attributes into caller owned memory.
+fn copy_item_attributes(
+    attributes: &ItemAttributes,
+) -> Option<opc_da_bindings::tagOPCITEMATTRIBUTES> {
+    let wide = |value: &str| value.encode_utf16().chain([0]).collect::<Vec<_>>();
+
+    let mut copy = opc_da_bindings::tagOPCITEMATTRIBUTES {
+        bActive: attributes.active.into(),
+        hClient: attributes.client_handle,
+        hServer: attributes.server_handle,
+        dwAccessRights: attributes.access_rights,
+        dwBlobSize: attributes.blob.len() as u32,
+        vtRequestedDataType: attributes.requested_data_type,
+        vtCanonicalDataType: attributes.canonical_data_type,
+        dwEUType: attributes.eu_type.to_native(),
+        vEUInfo: attributes.eu_info.clone(),
+        ..Default::default()
+    };
+
+    let pointers = (
+        co_task_mem_copy(&wide(&attributes.access_path)),
+        co_task_mem_copy(&wide(&attributes.item_id)),
+        co_task_mem_copy(&attributes.blob),
+    );
+    copy.szAccessPath = PWSTR(pointers.0.unwrap_or_default());
+    copy.szItemID = PWSTR(pointers.1.unwrap_or_default());
+    copy.pBlob = pointers.2.unwrap_or_default();
+
+    match pointers {
+        (Some(_), Some(_), Some(_)) => Some(copy),
+        _ => {
+            free_item_attributes(copy);
+            None
+        }
+    }
+}
+
 impl IEnumString_Impl for StringEnumerator_Impl {
     fn Next(
         &self,
@@ -156,19 +243,38 @@ impl IEnumString_Impl for StringEnumerator_Impl {
             Err(e) => return e.code(),
         };
 
-        let mut strings = Vec::with_capacity(count as usize);
-
-        let code = self.0.next(count, fetched, &mut strings);
-        if code != S_OK {
-            return code;
+        // Copy every string before touching the output buffer, so a failed
+        // allocation can free the earlier copies instead of leaking them.
+        let strings = self.0.reserve(count);
+        let mut copies: Vec<PWSTR> = Vec::with_capacity(strings.len());
+        for string in strings {
+            let pointer =
+                unsafe { CoTaskMemAlloc(core::mem::size_of_val(string.as_slice())) }.cast::<u16>();
+            if pointer.is_null() {
+                for copy in copies {
+                    unsafe { CoTaskMemFree(Some(copy.0.cast())) };
+                }
+
+                *fetched = 0;
+                return E_OUTOFMEMORY;
+            }
+
+            unsafe { core::ptr::copy_nonoverlapping(string.as_ptr(), pointer, string.len()) };
+            copies.push(PWSTR(pointer));
         }
 
-        for (i, string) in strings.iter_mut().enumerate() {
-            let pwstr = PWSTR(string.as_mut_ptr());
-            elements[i] = pwstr;
+        *fetched = copies.len() as u32;
+        let code = if copies.len() == count as usize {
+            S_OK
+        } else {
+            S_FALSE
+        };
+
+        for (element, copy) in elements.iter_mut().zip(copies) {
+            *element = copy;
         }
 
-        S_OK
+        code
     }
 
     fn Skip(&self, count: u32) -> windows::core::HRESULT {
@@ -201,18 +307,8 @@ impl IEnumUnknown_Impl for UnknownEnumerator_Impl {
             Err(e) => return e.code(),
         };
 
-        let mut items = Vec::with_capacity(count as usize);
-
-        let code = self.0.next(count, fetched, &mut items);
-        if code != S_OK {
-            return code;
-        }
-
-        for (i, unk) in items.into_iter().enumerate() {
-            elements[i] = Some(unk);
-        }
-
-        S_OK
+        self.0
+            .next_with(count, fetched, elements, |item| Some(item.clone()))
     }
 
     fn Skip(&self, count: u32) -> windows::core::Result<()> {
@@ -245,18 +341,8 @@ impl IEnumConnectionPoints_Impl for ConnectionPointsEnumerator_Impl {
             Err(e) => return e.code(),
         };
 
-        let mut items = Vec::with_capacity(count as usize);
-
-        let code = self.0.next(count, fetched, &mut items);
-        if code != S_OK {
-            return code;
-        }
-
-        for (i, cp) in items.into_iter().enumerate() {
-            elements[i] = Some(cp);
-        }
-
-        S_OK
+        self.0
+            .next_with(count, fetched, elements, |item| Some(item.clone()))
     }
 
     fn Skip(&self, count: u32) -> windows::core::Result<()> {
@@ -317,12 +403,37 @@ impl opc_da_bindings::IEnumOPCItemAttributes_Impl for ItemAttributesEnumerator_I
         fetched_count: *mut u32,
     ) -> windows::core::Result<()> {
         let fetched = fetched_count.into_ref()?;
-        let elements = items.into_com_array_ref(count)?;
 
-        safe_call! {
-            self.0.next(count, fetched, elements).ok(),
-            items
+        // Copy every item before allocating the output array, so a failed
+        // allocation can free the earlier copies instead of leaking them.
+        let attributes = self.0.reserve(count);
+        let mut copies = Vec::with_capacity(attributes.len());
+        for attributes in attributes {
+            match copy_item_attributes(attributes) {
+                Some(copy) => copies.push(copy),
+                None => {
+                    copies.into_iter().for_each(free_item_attributes);
+                    *fetched = 0;
+                    return Err(E_OUTOFMEMORY.into());
+                }
+            }
         }
+
+        let elements = match items.into_com_array_ref(copies.len() as u32) {
+            Ok(elements) => elements,
+            Err(error) => {
+                copies.into_iter().for_each(free_item_attributes);
+                *fetched = 0;
+                return Err(error);
+            }
+        };
+
+        *fetched = copies.len() as u32;
+        for (element, copy) in elements.iter_mut().zip(copies) {
+            unsafe { core::ptr::write(element, copy) };
+        }
+
+        Ok(())
     }
 
     fn Skip(&self, count: u32) -> windows::core::Result<()> {
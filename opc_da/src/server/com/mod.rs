@@ -8,9 +8,13 @@ pub mod group;
 pub mod item;
 #[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub mod memory;
+pub mod request;
 #[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub mod server;
 #[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub mod utils;
 #[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub mod variant;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+
+use crate::error;
+
+/// Tracks outstanding asynchronous requests of a group by cancel id.
+///
+/// A request is registered with [`AsyncRequests::begin`] before its callback
+/// is fired, and removed again by [`AsyncRequests::finish`]. A request
+/// cancelled in between via [`AsyncRequests::cancel`] reports `false` from
+/// `finish`, so the caller skips the data callback.
+#[derive(Debug, Default)]
+pub struct AsyncRequests {
+    next_cancel_id: core::sync::atomic::AtomicU32,
+    pending: std::sync::Mutex<BTreeMap<u32, u32>>,
+    refreshes: std::sync::Mutex<Vec<PendingRefresh>>,
+}
+
+/// A refresh whose data callback has not been fired yet.
+///
+/// Queued by [`AsyncRequests::queue_refresh`] and completed in the group's
+/// apartment through [`GroupTrait::complete_refreshes`](crate::server::traits::GroupTrait::complete_refreshes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingRefresh {
+    pub cancel_id: u32,
+    pub transaction_id: u32,
+    pub max_age: u32,
+}
+
+impl AsyncRequests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a request and returns its cancel id.
+    pub fn begin(&self, transaction_id: u32) -> u32 {
+        let cancel_id = self
+            .next_cancel_id
+            .fetch_add(1, core::sync::atomic::Ordering::SeqCst);
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(cancel_id, transaction_id);
+        cancel_id
+    }
+
+    /// Cancels a pending request.
+    ///
+    /// # Returns
+    /// The transaction id the request was started with.
+    ///
+    /// # Errors
+    /// Returns `E_FAIL` if the cancel id is unknown or already completed.
+    pub fn cancel(&self, cancel_id: u32) -> windows::core::Result<u32> {
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&cancel_id)
+            .ok_or_else(|| error::fail("Unknown or completed cancel id"))
+    }
+
+    /// Completes a request.
+    ///
+    /// # Returns
+    /// `true` if the request was still pending, `false` if it was cancelled.
+    pub fn finish(&self, cancel_id: u32) -> bool {
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&cancel_id)
+            .is_some()
+    }
+
+    /// Registers a refresh and queues it until the group completes it.
+    ///
+    /// # Returns
+    /// The cancel id of the refresh.
+    pub fn queue_refresh(&self, transaction_id: u32, max_age: u32) -> u32 {
+        let cancel_id = self.begin(transaction_id);
+        self.refreshes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(PendingRefresh {
+                cancel_id,
+                transaction_id,
+                max_age,
+            });
+        cancel_id
+    }
+
+    /// Removes and returns all queued refreshes in queue order.
+    pub fn take_refreshes(&self) -> Vec<PendingRefresh> {
+        core::mem::take(
+            &mut *self
+                .refreshes
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        )
+    }
+}
@@ -7,8 +7,9 @@ use super::{
     enumeration::{ConnectionPointsEnumerator, StringEnumerator},
     utils::{
         PointerReader, PointerWriter, TryReadArray, TryWriteArrayPointer, TryWriteInto,
-        TryWritePointer, TryWriteTo,
+        TryWritePointer, TryWriteTo, ensure_out_params,
     },
+    variant::VariantCell,
 };
 
 #[windows::core::implement(
@@ -34,6 +35,21 @@ impl<T: ServerTrait + 'static> core::ops::Deref for Server<T> {
     }
 }
 
+/// Rejects deadbands outside `0.0..=100.0`, including NaN and infinities.
+pub(super) fn validate_percent_deadband(
+    percent_deadband: Option<f32>,
+) -> windows::core::Result<Option<f32>> {
+    match percent_deadband {
+        Some(value) if !value.is_finite() || !(0.0..=100.0).contains(&value) => {
+            Err(windows::core::Error::new(
+                windows::Win32::Foundation::E_INVALIDARG,
+                "Percent deadband must be between 0 and 100",
+            ))
+        }
+        _ => Ok(percent_deadband),
+    }
+}
+
 // 1.0 required
 // 2.0 required
 // 3.0 required
@@ -52,13 +68,19 @@ impl<T: ServerTrait + 'static> opc_da_bindings::IOPCServer_Impl for Server_Impl<
         reference_interface_id: *const windows::core::GUID,
         unknown: windows::core::OutRef<'_, windows::core::IUnknown>,
     ) -> windows::core::Result<()> {
+        // Validate every out-param up front: once `add_group` succeeds the group
+        // exists, so a failed write afterwards would leave an unreachable group.
+        // `OutRef` is a transparent wrapper around the out-param pointer.
+        let unknown_ptr = unsafe { *(&unknown as *const _ as *const *const core::ffi::c_void) };
+        ensure_out_params([server_group.cast(), revised_update_rate.cast(), unknown_ptr])?;
+
         let info = self.add_group(
             unsafe { name.to_string() }?,
             active.as_bool(),
             requested_update_rate,
             client_group,
             unsafe { time_bias.as_ref() }.copied(),
-            unsafe { percent_deadband.as_ref() }.copied(),
+            validate_percent_deadband(unsafe { percent_deadband.as_ref() }.copied())?,
             locale_id,
             unsafe { reference_interface_id.as_ref() }.map(|id| id.to_u128()),
         )?;
@@ -136,6 +158,7 @@ impl<T: ServerTrait + 'static> opc_comn_bindings::IOPCCommon_Impl for Server_Imp
         count: *mut u32,
         locale_ids: *mut *mut u32,
     ) -> windows::core::Result<()> {
+        ensure_out_params([count.cast(), locale_ids.cast()])?;
         let available_locale_ids = self.query_available_locale_ids()?;
         PointerWriter::try_write(available_locale_ids.len() as _, count)?;
         PointerWriter::try_write_array_pointer(&available_locale_ids, locale_ids)?;
@@ -202,6 +225,12 @@ impl<T: ServerTrait + 'static> opc_da_bindings::IOPCItemProperties_Impl for Serv
         descriptions: *mut *mut windows::core::PWSTR,
         data_types: *mut *mut u16,
     ) -> windows::core::Result<()> {
+        ensure_out_params([
+            count.cast(),
+            property_ids.cast(),
+            descriptions.cast(),
+            data_types.cast(),
+        ])?;
         let vec = self.query_available_properties(unsafe { item_id.to_string() }?)?;
 
         PointerWriter::try_write(vec.len() as _, count)?;
@@ -234,6 +263,7 @@ impl<T: ServerTrait + 'static> opc_da_bindings::IOPCItemProperties_Impl for Serv
         data: *mut *mut windows::Win32::System::Variant::VARIANT,
         errors: *mut *mut windows::core::HRESULT,
     ) -> windows::core::Result<()> {
+        ensure_out_params([data.cast(), errors.cast()])?;
         let property_ids = PointerReader::try_read_array(count, property_ids)?;
 
         let vec = self.get_item_properties(unsafe { item_id.to_string() }?, property_ids)?;
@@ -243,10 +273,12 @@ impl<T: ServerTrait + 'static> opc_da_bindings::IOPCItemProperties_Impl for Serv
             errors,
         )?;
 
-        PointerWriter::try_write_array_pointer(
-            &vec.into_iter().map(|p| p.data.into()).collect::<Vec<_>>(),
-            data,
-        )?;
+        let values = vec
+            .into_iter()
+            .map(|p| VariantCell::new(p.data))
+            .collect::<Vec<_>>();
+        PointerWriter::try_write_array_pointer(VariantCell::as_slice(&values), data)?;
+        VariantCell::release(values);
 
         Ok(())
     }
@@ -259,6 +291,7 @@ impl<T: ServerTrait + 'static> opc_da_bindings::IOPCItemProperties_Impl for Serv
         new_item_ids: *mut *mut windows::core::PWSTR,
         errors: *mut *mut windows::core::HRESULT,
     ) -> windows::core::Result<()> {
+        ensure_out_params([new_item_ids.cast(), errors.cast()])?;
         let property_ids = PointerReader::try_read_array(count, property_ids)?;
 
         let vec = self.lookup_item_ids(unsafe { item_id.to_string() }?, property_ids)?;
@@ -292,6 +325,7 @@ impl<T: ServerTrait + 'static> opc_da_bindings::IOPCBrowse_Impl for Server_Impl<
         property_ids: *const u32,
         item_properties: *mut *mut opc_da_bindings::tagOPCITEMPROPERTIES,
     ) -> windows::core::Result<()> {
+        ensure_out_params([item_properties.cast()])?;
         let item_ids = PointerReader::try_read_array(item_count, item_ids)?;
         let property_ids = PointerReader::try_read_array(property_count, property_ids)?;
 
@@ -331,6 +365,12 @@ impl<T: ServerTrait + 'static> opc_da_bindings::IOPCBrowse_Impl for Server_Impl<
         count: *mut u32,
         browse_elements: *mut *mut opc_da_bindings::tagOPCBROWSEELEMENT,
     ) -> windows::core::Result<()> {
+        ensure_out_params([
+            continuation_point.cast(),
+            more_elements.cast(),
+            count.cast(),
+            browse_elements.cast(),
+        ])?;
         let item_id = unsafe { item_id.to_string()? };
         let element_name_filter = unsafe { element_name_filter.to_string()? };
         let vendor_filter = unsafe { vendor_filter.to_string()? };
@@ -485,6 +525,12 @@ impl<T: ServerTrait + 'static> opc_da_bindings::IOPCItemIO_Impl for Server_Impl<
         timestamps: *mut *mut windows::Win32::Foundation::FILETIME,
         errors: *mut *mut windows::core::HRESULT,
     ) -> windows::core::Result<()> {
+        ensure_out_params([
+            values.cast(),
+            qualities.cast(),
+            timestamps.cast(),
+            errors.cast(),
+        ])?;
         let item_ids = PointerReader::try_read_array(count, item_ids)?;
         let max_ages = PointerReader::try_read_array(count, max_ages)?;
 
@@ -496,13 +542,12 @@ impl<T: ServerTrait + 'static> opc_da_bindings::IOPCItemIO_Impl for Server_Impl<
                 .collect(),
         )?;
 
-        PointerWriter::try_write_array_pointer(
-            &result
-                .iter()
-                .map(|vqt| vqt.value.clone().into())
-                .collect::<Vec<_>>(),
-            values,
-        )?;
+        let cells = result
+            .iter()
+            .map(|vqt| VariantCell::new(vqt.value.clone()))
+            .collect::<Vec<_>>();
+        PointerWriter::try_write_array_pointer(VariantCell::as_slice(&cells), values)?;
+        VariantCell::release(cells);
 
         PointerWriter::try_write_array_pointer(
             &result.iter().map(|vqt| vqt.quality).collect::<Vec<_>>(),
@@ -532,6 +577,7 @@ impl<T: ServerTrait + 'static> opc_da_bindings::IOPCItemIO_Impl for Server_Impl<
         item_vqt: *const opc_da_bindings::tagOPCITEMVQT,
         errors: *mut *mut windows::core::HRESULT,
     ) -> windows::core::Result<()> {
+        ensure_out_params([errors.cast()])?;
         let item_ids = PointerReader::try_read_array(count, item_ids)?;
         let item_vqt = PointerReader::try_read_array(count, item_vqt)?
             .into_iter()
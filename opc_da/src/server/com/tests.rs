@@ -0,0 +1,409 @@
+use windows::Win32::Foundation::E_INVALIDARG;
+
+use super::{server::validate_percent_deadband, utils::ensure_out_params};
+
+#[test]
+fn test_percent_deadband_rejects_nan() {
+    let error = validate_percent_deadband(Some(f32::NAN)).unwrap_err();
+    assert_eq!(error.code(), E_INVALIDARG);
+
+    let error = validate_percent_deadband(Some(f32::INFINITY)).unwrap_err();
+    assert_eq!(error.code(), E_INVALIDARG);
+
+    assert!(validate_percent_deadband(Some(100.5)).is_err());
+    assert_eq!(validate_percent_deadband(Some(50.0)).unwrap(), Some(50.0));
+    assert_eq!(validate_percent_deadband(None).unwrap(), None);
+}
+
+#[test]
+fn test_ensure_out_params() {
+    let mut count = 0u32;
+    let mut values = core::ptr::null_mut::<u16>();
+    let count_ptr: *mut u32 = &mut count;
+    let values_ptr: *mut *mut u16 = &mut values;
+
+    assert!(ensure_out_params([count_ptr.cast(), values_ptr.cast()]).is_ok());
+
+    let error = ensure_out_params([count_ptr.cast(), core::ptr::null()]).unwrap_err();
+    assert_eq!(error.code(), windows::Win32::Foundation::E_POINTER);
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_mock_server_read_write_browse() {
+    use crate::{
+        def::Variant,
+        server::{
+            mock::{MockServer, OPC_E_UNKNOWNITEMID},
+            traits::{BrowseFilter, ItemOptionalVqt, ItemVqt, ItemWithMaxAge, ServerTrait as _},
+        },
+    };
+    use windows::Win32::Foundation::E_FAIL;
+
+    let server = MockServer::new()
+        .with_item("Plant.Line1.Speed", Variant::F64(1.5))
+        .with_item("Plant.Line1.Running", Variant::Bool(true))
+        .with_item("Plant.Name", Variant::String("A".to_string()));
+    server.fail_read("Plant.Line1.Running", E_FAIL);
+
+    let read = |item_id: &str| ItemWithMaxAge {
+        item_id: item_id.to_string(),
+        max_age: 0,
+    };
+    let results = server
+        .read(vec![
+            read("Plant.Line1.Speed"),
+            read("Plant.Line1.Running"),
+            read("Missing"),
+        ])
+        .unwrap();
+    assert_eq!(results[0].value, Variant::F64(1.5));
+    assert_eq!(results[1].error, E_FAIL);
+    assert_eq!(results[2].error, OPC_E_UNKNOWNITEMID);
+
+    let errors = server
+        .write_vqt(vec![ItemOptionalVqt {
+            item_id: "Plant.Line1.Speed".to_string(),
+            optional_vqt: ItemVqt {
+                value: Variant::F64(2.0),
+                quality: None,
+                timestamp: None,
+            },
+        }])
+        .unwrap();
+    assert!(errors[0].is_ok());
+    assert_eq!(
+        server.item("Plant.Line1.Speed").unwrap().value,
+        Variant::F64(2.0)
+    );
+    assert_eq!(server.writes().len(), 1);
+
+    let browse = |item_id: &str, filter| {
+        server
+            .browse(
+                item_id.to_string(),
+                None,
+                0,
+                filter,
+                String::new(),
+                String::new(),
+                false,
+                false,
+                vec![],
+            )
+            .unwrap()
+            .elements
+            .into_iter()
+            .map(|element| element.item_id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(browse("", BrowseFilter::All), vec!["Plant"]);
+    assert_eq!(browse("Plant", BrowseFilter::Branches), vec!["Plant.Line1"]);
+    assert_eq!(browse("Plant", BrowseFilter::Items), vec!["Plant.Name"]);
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_mock_server_groups() {
+    use windows::core::Interface as _;
+
+    use crate::{
+        def::EnumScope,
+        server::{
+            mock::{MockServer, OPC_E_DUPLICATENAME},
+            traits::ServerTrait as _,
+        },
+    };
+
+    let server = MockServer::new();
+    let add = |name: &str| {
+        server.add_group(
+            name.to_string(),
+            true,
+            500,
+            7,
+            None,
+            None,
+            0,
+            Some(opc_da_bindings::IOPCItemMgt::IID.to_u128()),
+        )
+    };
+
+    let info = add("Group").unwrap();
+    assert_eq!(info.revised_update_rate, 500);
+    assert!(info.unknown.cast::<opc_da_bindings::IOPCItemMgt>().is_ok());
+    assert_eq!(
+        add("Group").err().map(|error| error.code()),
+        Some(OPC_E_DUPLICATENAME)
+    );
+    assert!(add("").is_ok());
+    assert_eq!(server.get_status().unwrap().group_count, 2);
+
+    assert!(server.get_group_by_name("Group".to_string(), None).is_ok());
+    assert!(
+        server
+            .get_group_by_name("Missing".to_string(), None)
+            .is_err()
+    );
+    let names: windows::Win32::System::Com::IEnumString = server
+        .create_group_enumerator(
+            EnumScope::All,
+            Some(windows::Win32::System::Com::IEnumString::IID.to_u128()),
+        )
+        .unwrap()
+        .cast()
+        .unwrap();
+    let mut name = [windows::core::PWSTR::null(); 2];
+    let mut fetched = 0;
+    unsafe { names.Next(&mut name, Some(&mut fetched)) }
+        .ok()
+        .unwrap();
+    assert_eq!(fetched, 2);
+    for name in name {
+        unsafe { windows::Win32::System::Com::CoTaskMemFree(Some(name.0 as _)) };
+    }
+
+    server.remove_group(info.server_group, false).unwrap();
+    assert!(server.group("Group").is_none());
+    assert!(server.remove_group(info.server_group, false).is_err());
+}
+
+/// `(group client handle, item client handle, value, quality)` per data change.
+#[cfg(feature = "test-util")]
+type RecordedChanges = std::sync::Arc<std::sync::Mutex<Vec<(u32, u32, crate::def::Variant, u16)>>>;
+
+#[cfg(feature = "test-util")]
+#[windows::core::implement(opc_da_bindings::IOPCDataCallback)]
+struct RecordingCallback(RecordedChanges);
+
+#[cfg(feature = "test-util")]
+impl opc_da_bindings::IOPCDataCallback_Impl for RecordingCallback_Impl {
+    fn OnDataChange(
+        &self,
+        _transaction_id: u32,
+        group: u32,
+        _master_quality: windows::core::HRESULT,
+        _master_error: windows::core::HRESULT,
+        count: u32,
+        client_items: *const u32,
+        values: *const windows::Win32::System::Variant::VARIANT,
+        qualities: *const u16,
+        _timestamps: *const windows::Win32::Foundation::FILETIME,
+        _errors: *const windows::core::HRESULT,
+    ) -> windows::core::Result<()> {
+        let mut changes = self.0.lock().unwrap();
+        for index in 0..count as usize {
+            unsafe {
+                changes.push((
+                    group,
+                    *client_items.add(index),
+                    crate::def::Variant::from(&*values.add(index)),
+                    *qualities.add(index),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn OnReadComplete(
+        &self,
+        _transaction_id: u32,
+        _group: u32,
+        _master_quality: windows::core::HRESULT,
+        _master_error: windows::core::HRESULT,
+        _count: u32,
+        _client_items: *const u32,
+        _values: *const windows::Win32::System::Variant::VARIANT,
+        _qualities: *const u16,
+        _timestamps: *const windows::Win32::Foundation::FILETIME,
+        _errors: *const windows::core::HRESULT,
+    ) -> windows::core::Result<()> {
+        Ok(())
+    }
+
+    fn OnWriteComplete(
+        &self,
+        _transaction_id: u32,
+        _group: u32,
+        _master_error: windows::core::HRESULT,
+        _count: u32,
+        _client_handles: *const u32,
+        _errors: *const windows::core::HRESULT,
+    ) -> windows::core::Result<()> {
+        Ok(())
+    }
+
+    fn OnCancelComplete(&self, _transaction_id: u32, _group: u32) -> windows::core::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_mock_group_push_value() {
+    use windows::core::Interface as _;
+
+    use crate::{
+        def::Variant,
+        server::{mock::MockGroup, traits::GroupTrait as _},
+    };
+
+    let group = MockGroup::new("Group", 7, 1).into_object();
+
+    let mut item_id: Vec<u16> = "Tag.Speed".encode_utf16().chain(Some(0)).collect();
+    let definition = opc_da_bindings::tagOPCITEMDEF {
+        szItemID: windows::core::PWSTR(item_id.as_mut_ptr()),
+        bActive: true.into(),
+        hClient: 42,
+        ..Default::default()
+    };
+    let mut results = [opc_da_bindings::tagOPCITEMRESULT::default()];
+    let mut errors = [windows::core::HRESULT::default()];
+    group
+        .add_items(&[definition], &mut results, &mut errors)
+        .unwrap();
+    assert!(errors[0].is_ok());
+
+    let changes = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+    let callback: opc_da_bindings::IOPCDataCallback = RecordingCallback(changes.clone()).into();
+    let connection_point = group
+        .find_connection_point(&opc_da_bindings::IOPCDataCallback::IID)
+        .unwrap();
+    unsafe { connection_point.Advise(&callback) }.unwrap();
+
+    group
+        .push_value("Tag.Speed", Variant::F64(12.5), 0xC0)
+        .unwrap();
+    assert_eq!(
+        *changes.lock().unwrap(),
+        vec![(7, 42, Variant::F64(12.5), 0xC0)]
+    );
+
+    assert!(group.push_value("Missing", Variant::I32(1), 0xC0).is_err());
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_mock_group_refresh_is_queued() {
+    use windows::core::Interface as _;
+
+    use crate::{
+        def::Variant,
+        server::{mock::MockGroup, traits::GroupTrait as _},
+    };
+
+    let group = MockGroup::new("Group", 7, 1).into_object();
+
+    let mut item_id: Vec<u16> = "Tag.Speed".encode_utf16().chain(Some(0)).collect();
+    let definition = opc_da_bindings::tagOPCITEMDEF {
+        szItemID: windows::core::PWSTR(item_id.as_mut_ptr()),
+        bActive: true.into(),
+        hClient: 42,
+        ..Default::default()
+    };
+    let mut results = [opc_da_bindings::tagOPCITEMRESULT::default()];
+    let mut errors = [windows::core::HRESULT::default()];
+    group
+        .add_items(&[definition], &mut results, &mut errors)
+        .unwrap();
+    group
+        .set_value("Tag.Speed", Variant::F64(3.0), 0xC0)
+        .unwrap();
+
+    let changes = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+    let callback: opc_da_bindings::IOPCDataCallback = RecordingCallback(changes.clone()).into();
+    let connection_point = group
+        .find_connection_point(&opc_da_bindings::IOPCDataCallback::IID)
+        .unwrap();
+    unsafe { connection_point.Advise(&callback) }.unwrap();
+
+    // The cancel id is returned before the callback, so it can be cancelled.
+    let cancel_id = group.refresh_max_age(0, 1).unwrap();
+    assert!(changes.lock().unwrap().is_empty());
+    group.cancel2(cancel_id).unwrap();
+    group.complete_refreshes();
+    assert!(changes.lock().unwrap().is_empty());
+
+    group.refresh_max_age(0, 2).unwrap();
+    group.complete_refreshes();
+    assert_eq!(
+        *changes.lock().unwrap(),
+        vec![(7, 42, Variant::F64(3.0), 0xC0)]
+    );
+}
+
+#[test]
+fn test_item_vqt_native_round_trip() {
+    use crate::{def::Variant, server::traits::ItemVqt};
+
+    let timestamp = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+    let native = ItemVqt {
+        value: Variant::I32(5),
+        quality: Some(0xC0),
+        timestamp: Some(timestamp),
+    }
+    .into_native()
+    .unwrap();
+    assert!(native.bQualitySpecified.as_bool());
+    assert!(native.bTimeStampSpecified.as_bool());
+    assert_eq!((native.wReserved, native.dwReserved), (0, 0));
+
+    let vqt = ItemVqt::try_from(native).unwrap();
+    assert_eq!(vqt.value, Variant::I32(5));
+    assert_eq!(vqt.quality, Some(0xC0));
+    assert_eq!(vqt.timestamp, Some(timestamp));
+
+    let native = ItemVqt {
+        value: Variant::Empty,
+        quality: None,
+        timestamp: None,
+    }
+    .into_native()
+    .unwrap();
+    assert!(!native.bQualitySpecified.as_bool());
+    assert!(!native.bTimeStampSpecified.as_bool());
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_mock_group_released_with_connection_point() {
+    use windows::core::Interface as _;
+
+    use crate::server::{mock::MockGroup, traits::GroupTrait as _};
+
+    let group = MockGroup::new("Group", 7, 1).into_object();
+    let connection_point = group
+        .find_connection_point(&opc_da_bindings::IOPCDataCallback::IID)
+        .unwrap();
+    assert!(unsafe { connection_point.GetConnectionPointContainer() }.is_ok());
+
+    // The connection point must not keep its group alive.
+    drop(group);
+    assert!(unsafe { connection_point.GetConnectionPointContainer() }.is_err());
+}
+
+#[test]
+fn test_item_property_to_native() {
+    use crate::server::traits::ItemProperty;
+
+    let mut native = opc_da_bindings::tagOPCITEMPROPERTY::try_from(ItemProperty {
+        data_type: windows::Win32::System::Variant::VT_I4.0,
+        property_id: 5000,
+        item_id: "Tag.Speed.Unit".to_string(),
+        description: "Unit".to_string(),
+        value: crate::def::Variant::I32(1),
+        error_id: windows::core::HRESULT(0),
+    })
+    .unwrap();
+    assert_eq!(
+        unsafe { native.szItemID.to_string() }.unwrap(),
+        "Tag.Speed.Unit"
+    );
+    assert_eq!(unsafe { native.szDescription.to_string() }.unwrap(), "Unit");
+
+    unsafe {
+        windows::Win32::System::Com::CoTaskMemFree(Some(native.szItemID.0 as _));
+        windows::Win32::System::Com::CoTaskMemFree(Some(native.szDescription.0 as _));
+        let _ = windows::Win32::System::Variant::VariantClear(&mut native.vValue);
+    }
+}
@@ -2,6 +2,23 @@ pub struct PointerReader;
 
 pub struct PointerWriter;
 
+/// Fails with `E_POINTER` if any required out-param is null.
+///
+/// COM methods call this before doing any work, so a bad call neither runs
+/// the operation nor leaks the arrays already written to valid out-params.
+pub fn ensure_out_params<const N: usize>(
+    pointers: [*const core::ffi::c_void; N],
+) -> windows::core::Result<()> {
+    if pointers.iter().any(|pointer| pointer.is_null()) {
+        return Err(windows::core::Error::new(
+            windows::Win32::Foundation::E_POINTER,
+            "Null pointer passed for a required out-param",
+        ));
+    }
+
+    Ok(())
+}
+
 pub trait TryWritePointer<T> {
     type Error;
 
@@ -201,9 +218,9 @@ impl<T: AsRef<str>> TryWriteTo<T, windows::core::PWSTR> for PointerWriter {
     type Error = windows::core::Error;
 
     fn try_write_to(value: T) -> windows::core::Result<windows::core::PWSTR> {
-        let ptr: *mut windows::core::PWSTR = core::ptr::null_mut();
-        Self::try_write_into(value, ptr)?;
-        Ok(unsafe { *ptr })
+        let mut pwstr = windows::core::PWSTR::null();
+        Self::try_write_into(value, &mut pwstr as *mut _)?;
+        Ok(pwstr)
     }
 }
 
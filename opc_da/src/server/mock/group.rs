@@ -0,0 +1,805 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, OnceLock},
+};
+
+use windows::core::Interface as _;
+
+use crate::{
+    def::{EuType, ItemAttributes, Variant},
+    error,
+    server::{
+        com::{
+            connection_point::DataCallbackConnectionPoint,
+            group::Group,
+            request::AsyncRequests,
+            utils::{PointerWriter, TryWriteInto as _},
+            variant::VariantCell,
+        },
+        traits::GroupTrait,
+    },
+    utils::{TryFromNative as _, TryToNative as _},
+};
+
+use super::{OPC_E_INVALIDHANDLE, OPC_E_UNKNOWNITEMID, QUALITY_GOOD};
+
+/// An item added to a [`MockGroup`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockGroupItem {
+    pub item_id: String,
+    pub client_handle: u32,
+    pub active: bool,
+    pub requested_data_type: u16,
+    pub value: Variant,
+    pub quality: u16,
+    pub timestamp: std::time::SystemTime,
+}
+
+struct MockGroupState {
+    name: String,
+    active: bool,
+    enabled: bool,
+    update_rate: u32,
+    client_handle: u32,
+    time_bias: i32,
+    percent_deadband: f32,
+    locale_id: u32,
+    next_server_handle: u32,
+    items: BTreeMap<u32, MockGroupItem>,
+}
+
+/// An in-memory OPC DA group that fires data changes on demand.
+///
+/// Any item ID can be added. Items start out `Empty` with bad quality until a
+/// value is set through [`set_value`](Self::set_value) or
+/// [`push_value`](Self::push_value).
+///
+/// Refreshes stay queued until [`GroupTrait::complete_refreshes`] is called.
+pub struct MockGroup {
+    state: Mutex<MockGroupState>,
+    server_handle: u32,
+    requests: AsyncRequests,
+    connection_point: OnceLock<windows::core::ComObject<DataCallbackConnectionPoint>>,
+}
+
+impl MockGroup {
+    /// Creates an active, enabled group.
+    pub fn new<S: Into<String>>(name: S, client_handle: u32, server_handle: u32) -> Self {
+        Self {
+            state: Mutex::new(MockGroupState {
+                name: name.into(),
+                active: true,
+                enabled: true,
+                update_rate: 1000,
+                client_handle,
+                time_bias: 0,
+                percent_deadband: 0.0,
+                locale_id: 0,
+                next_server_handle: 1,
+                items: BTreeMap::new(),
+            }),
+            server_handle,
+            requests: AsyncRequests::new(),
+            connection_point: OnceLock::new(),
+        }
+    }
+
+    /// Wraps the group in a COM object with an `IOPCDataCallback` connection point.
+    pub fn into_object(self) -> windows::core::ComObject<Group<MockGroup>> {
+        let object = windows::core::ComObject::new(Group(self));
+        let container: windows::Win32::System::Com::IConnectionPointContainer =
+            object.to_interface();
+        let connection_point = DataCallbackConnectionPoint::new(&container)
+            .expect("implemented objects support weak references");
+        let _ = object
+            .0
+            .connection_point
+            .set(windows::core::ComObject::new(connection_point));
+        object
+    }
+
+    /// Returns the current group name.
+    pub fn name(&self) -> String {
+        self.lock().name.clone()
+    }
+
+    /// Returns a copy of the item with the given ID.
+    pub fn item(&self, item_id: &str) -> Option<MockGroupItem> {
+        self.lock()
+            .items
+            .values()
+            .find(|item| item.item_id == item_id)
+            .cloned()
+    }
+
+    /// Sets the value of an added item without firing a callback.
+    ///
+    /// # Errors
+    /// Returns `OPC_E_UNKNOWNITEMID` if the item is not in the group.
+    pub fn set_value(
+        &self,
+        item_id: &str,
+        value: Variant,
+        quality: u16,
+    ) -> windows::core::Result<()> {
+        self.update(item_id, value, quality).map(|_| ())
+    }
+
+    /// Sets the value of an added item and fires `OnDataChange`.
+    ///
+    /// The callback is skipped if the group or item is inactive, the group is
+    /// disabled, or no sink is advised.
+    ///
+    /// # Errors
+    /// Returns `OPC_E_UNKNOWNITEMID` if the item is not in the group, or the
+    /// error returned by the sink.
+    pub fn push_value(
+        &self,
+        item_id: &str,
+        value: Variant,
+        quality: u16,
+    ) -> windows::core::Result<()> {
+        let Some((group_client_handle, item)) = self.update(item_id, value, quality)? else {
+            return Ok(());
+        };
+
+        let connection_point = match self.data_callback() {
+            Some(connection_point) if connection_point.has_sinks() => connection_point,
+            _ => return Ok(()),
+        };
+
+        let master_quality =
+            if quality & opc_da_bindings::OPC_QUALITY_MASK == opc_da_bindings::OPC_QUALITY_GOOD {
+                windows::Win32::Foundation::S_OK
+            } else {
+                windows::Win32::Foundation::S_FALSE
+            };
+        let values = [VariantCell::new(item.value)];
+
+        connection_point.broadcast_data_change(
+            0,
+            group_client_handle,
+            master_quality,
+            windows::Win32::Foundation::S_OK,
+            &[item.client_handle],
+            VariantCell::as_slice(&values),
+            &[item.quality],
+            &[item.timestamp.try_to_native()?],
+            &[windows::Win32::Foundation::S_OK],
+        )
+    }
+
+    /// Updates an item and returns it if a data change should be fired.
+    fn update(
+        &self,
+        item_id: &str,
+        value: Variant,
+        quality: u16,
+    ) -> windows::core::Result<Option<(u32, MockGroupItem)>> {
+        let mut state = self.lock();
+        let notify = state.active && state.enabled;
+        let group_client_handle = state.client_handle;
+
+        let item = state
+            .items
+            .values_mut()
+            .find(|item| item.item_id == item_id)
+            .ok_or_else(|| windows::core::Error::new(OPC_E_UNKNOWNITEMID, "Unknown item ID"))?;
+
+        item.value = value;
+        item.quality = quality;
+        item.timestamp = std::time::SystemTime::now();
+
+        Ok((notify && item.active).then(|| (group_client_handle, item.clone())))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockGroupState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Applies `f` to each item, writing `OPC_E_INVALIDHANDLE` for unknown handles.
+    fn for_each_item<F>(
+        &self,
+        item_server_handles: &[u32],
+        errors: &mut [windows::core::HRESULT],
+        mut f: F,
+    ) -> windows::core::Result<()>
+    where
+        F: FnMut(usize, &mut MockGroupItem) -> windows::core::Result<()>,
+    {
+        let mut state = self.lock();
+        for (index, handle) in item_server_handles.iter().enumerate() {
+            errors[index] = match state.items.get_mut(handle) {
+                Some(item) => f(index, item)
+                    .map_or_else(|error| error.code(), |_| windows::Win32::Foundation::S_OK),
+                None => OPC_E_INVALIDHANDLE,
+            };
+        }
+
+        Ok(())
+    }
+
+    fn new_item(
+        definition: &opc_da_bindings::tagOPCITEMDEF,
+    ) -> windows::core::Result<MockGroupItem> {
+        if definition.szItemID.is_null() {
+            return Err(error::invalid_arg("Item ID is null"));
+        }
+
+        let item_id = unsafe { definition.szItemID.to_string() }?;
+        if item_id.is_empty() {
+            return Err(windows::core::Error::new(
+                OPC_E_UNKNOWNITEMID,
+                "Item ID is empty",
+            ));
+        }
+
+        Ok(MockGroupItem {
+            item_id,
+            client_handle: definition.hClient,
+            active: definition.bActive.as_bool(),
+            requested_data_type: definition.vtRequestedDataType,
+            value: Variant::Empty,
+            quality: 0,
+            timestamp: std::time::UNIX_EPOCH,
+        })
+    }
+
+    fn item_result(server_handle: u32, item: &MockGroupItem) -> opc_da_bindings::tagOPCITEMRESULT {
+        opc_da_bindings::tagOPCITEMRESULT {
+            hServer: server_handle,
+            vtCanonicalDataType: item.value.get_data_type(),
+            dwAccessRights: opc_da_bindings::OPC_READABLE | opc_da_bindings::OPC_WRITEABLE,
+            ..Default::default()
+        }
+    }
+}
+
+impl GroupTrait for MockGroup {
+    fn add_items(
+        &self,
+        items: &[opc_da_bindings::tagOPCITEMDEF],
+        results: &mut [opc_da_bindings::tagOPCITEMRESULT],
+        errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        let mut state = self.lock();
+        for (index, definition) in items.iter().enumerate() {
+            match Self::new_item(definition) {
+                Ok(item) => {
+                    let server_handle = state.next_server_handle;
+                    state.next_server_handle += 1;
+                    results[index] = Self::item_result(server_handle, &item);
+                    errors[index] = windows::Win32::Foundation::S_OK;
+                    state.items.insert(server_handle, item);
+                }
+                Err(error) => errors[index] = error.code(),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_items(
+        &self,
+        items: &[opc_da_bindings::tagOPCITEMDEF],
+        _blob_update: windows_core::BOOL,
+        validation_results: &mut [opc_da_bindings::tagOPCITEMRESULT],
+        errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        for (index, definition) in items.iter().enumerate() {
+            match Self::new_item(definition) {
+                Ok(item) => {
+                    validation_results[index] = Self::item_result(0, &item);
+                    errors[index] = windows::Win32::Foundation::S_OK;
+                }
+                Err(error) => errors[index] = error.code(),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn remove_items(
+        &self,
+        item_server_handles: &[u32],
+        errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        let mut state = self.lock();
+        for (index, handle) in item_server_handles.iter().enumerate() {
+            errors[index] = match state.items.remove(handle) {
+                Some(_) => windows::Win32::Foundation::S_OK,
+                None => OPC_E_INVALIDHANDLE,
+            };
+        }
+
+        Ok(())
+    }
+
+    fn set_active_state(
+        &self,
+        item_server_handles: &[u32],
+        active: windows_core::BOOL,
+        errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        self.for_each_item(item_server_handles, errors, |_, item| {
+            item.active = active.as_bool();
+            Ok(())
+        })
+    }
+
+    fn set_client_handles(
+        &self,
+        item_server_handles: &[u32],
+        handle_client: &[u32],
+        errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        self.for_each_item(item_server_handles, errors, |index, item| {
+            item.client_handle = handle_client[index];
+            Ok(())
+        })
+    }
+
+    fn set_data_types(
+        &self,
+        item_server_handles: &[u32],
+        requested_data_types: &[u16],
+        errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        self.for_each_item(item_server_handles, errors, |index, item| {
+            item.requested_data_type = requested_data_types[index];
+            Ok(())
+        })
+    }
+
+    fn item_attributes(&self) -> windows::core::Result<Vec<ItemAttributes>> {
+        Ok(self
+            .lock()
+            .items
+            .iter()
+            .map(|(server_handle, item)| ItemAttributes {
+                access_path: String::new(),
+                item_id: item.item_id.clone(),
+                active: item.active,
+                client_handle: item.client_handle,
+                server_handle: *server_handle,
+                access_rights: opc_da_bindings::OPC_READABLE | opc_da_bindings::OPC_WRITEABLE,
+                blob: vec![],
+                requested_data_type: item.requested_data_type,
+                canonical_data_type: item.value.get_data_type(),
+                eu_type: EuType::NoEnum,
+                eu_info: Default::default(),
+            })
+            .collect())
+    }
+
+    fn get_state(
+        &self,
+        update_rate: &mut u32,
+        active: &mut windows_core::BOOL,
+        name: &mut windows::core::PWSTR,
+        time_bias: &mut i32,
+        percent_deadband: &mut f32,
+        locale_id: &mut u32,
+        group_client_handle: &mut u32,
+        item_server_handles_group: &mut u32,
+    ) -> windows::core::Result<()> {
+        let state = self.lock();
+        PointerWriter::try_write_into(&state.name, name as *mut _)?;
+        *update_rate = state.update_rate;
+        *active = state.active.into();
+        *time_bias = state.time_bias;
+        *percent_deadband = state.percent_deadband;
+        *locale_id = state.locale_id;
+        *group_client_handle = state.client_handle;
+        *item_server_handles_group = self.server_handle;
+
+        Ok(())
+    }
+
+    fn set_state(
+        &self,
+        requested_update_rate: &u32,
+        revised_update_rate: &mut u32,
+        active: &windows_core::BOOL,
+        time_bias: &i32,
+        percent_deadband: &f32,
+        locale_id: &u32,
+        group_client_handle: &u32,
+    ) -> windows::core::Result<()> {
+        let mut state = self.lock();
+        state.update_rate = *requested_update_rate;
+        state.active = active.as_bool();
+        state.time_bias = *time_bias;
+        state.percent_deadband = *percent_deadband;
+        state.locale_id = *locale_id;
+        state.client_handle = *group_client_handle;
+        *revised_update_rate = state.update_rate;
+
+        Ok(())
+    }
+
+    fn set_name(&self, name: &windows::core::PCWSTR) -> windows::core::Result<()> {
+        self.lock().name = unsafe { name.to_string() }?;
+        Ok(())
+    }
+
+    fn clone_group(
+        &self,
+        _name: &windows::core::PCWSTR,
+        _reference_interface_id: &windows::core::GUID,
+    ) -> windows::core::Result<windows::core::IUnknown> {
+        Err(error::not_impl("MockGroup does not support cloning"))
+    }
+
+    fn set_keep_alive(&self, _keep_alive_time: u32) -> windows::core::Result<u32> {
+        Err(error::not_impl("MockGroup does not support keep-alive"))
+    }
+
+    fn get_keep_alive(&self) -> windows::core::Result<u32> {
+        Err(error::not_impl("MockGroup does not support keep-alive"))
+    }
+
+    fn get_public_group_state(&self) -> windows::core::Result<windows_core::BOOL> {
+        Ok(false.into())
+    }
+
+    fn move_to_public(&self) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support public groups"))
+    }
+
+    fn read(
+        &self,
+        _source: opc_da_bindings::tagOPCDATASOURCE,
+        item_server_handles: &[u32],
+        item_values: &mut [opc_da_bindings::tagOPCITEMSTATE],
+        errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        self.for_each_item(item_server_handles, errors, |index, item| {
+            item_values[index] = opc_da_bindings::tagOPCITEMSTATE {
+                hClient: item.client_handle,
+                ftTimeStamp: item.timestamp.try_to_native()?,
+                wQuality: item.quality,
+                wReserved: 0,
+                vDataValue: item.value.clone().into(),
+            };
+            Ok(())
+        })
+    }
+
+    fn write(
+        &self,
+        item_server_handles: &[u32],
+        item_values: &[windows::Win32::System::Variant::VARIANT],
+        errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        self.for_each_item(item_server_handles, errors, |index, item| {
+            item.value = Variant::from(&item_values[index]);
+            item.quality = QUALITY_GOOD;
+            item.timestamp = std::time::SystemTime::now();
+            Ok(())
+        })
+    }
+
+    fn read_max_age(
+        &self,
+        item_server_handles: &[u32],
+        _max_age: &[u32],
+        values: &mut [windows::Win32::System::Variant::VARIANT],
+        qualities: &mut [u16],
+        timestamps: &mut [windows::Win32::Foundation::FILETIME],
+        errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        self.for_each_item(item_server_handles, errors, |index, item| {
+            timestamps[index] = item.timestamp.try_to_native()?;
+            qualities[index] = item.quality;
+            values[index] = item.value.clone().into();
+            Ok(())
+        })
+    }
+
+    fn write_vqt(
+        &self,
+        _count: u32,
+        item_server_handles: &[u32],
+        item_vqt: &[opc_da_bindings::tagOPCITEMVQT],
+        errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        self.for_each_item(item_server_handles, errors, |index, item| {
+            let vqt = &item_vqt[index];
+            item.timestamp = if vqt.bTimeStampSpecified.as_bool() {
+                std::time::SystemTime::try_from_native(&vqt.ftTimeStamp)?
+            } else {
+                std::time::SystemTime::now()
+            };
+            item.quality = if vqt.bQualitySpecified.as_bool() {
+                vqt.wQuality
+            } else {
+                QUALITY_GOOD
+            };
+            item.value = Variant::from(&vqt.vDataValue);
+            Ok(())
+        })
+    }
+
+    fn read2(
+        &self,
+        _item_server_handles: &[u32],
+        _transaction_id: u32,
+        _cancel_id: &mut u32,
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support async reads"))
+    }
+
+    fn write2(
+        &self,
+        _count: u32,
+        _item_server_handles: &[u32],
+        _item_values: &[windows::Win32::System::Variant::VARIANT],
+        _transaction_id: u32,
+        _cancel_id: &mut u32,
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support async writes"))
+    }
+
+    fn refresh2(
+        &self,
+        source: opc_da_bindings::tagOPCDATASOURCE,
+        transaction_id: u32,
+    ) -> windows::core::Result<u32> {
+        let max_age = if source == opc_da_bindings::OPC_DS_DEVICE {
+            0
+        } else {
+            u32::MAX
+        };
+
+        self.refresh_max_age(max_age, transaction_id)
+    }
+
+    fn set_enable(&self, enable: windows_core::BOOL) -> windows::core::Result<()> {
+        self.lock().enabled = enable.as_bool();
+        Ok(())
+    }
+
+    fn get_enable(&self) -> windows::core::Result<windows_core::BOOL> {
+        Ok(self.lock().enabled.into())
+    }
+
+    fn read_max_age2(
+        &self,
+        _item_server_handles: &[u32],
+        _max_age: &[u32],
+        _transaction_id: u32,
+        _cancel_id: &mut u32,
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support async reads"))
+    }
+
+    fn write_vqt2(
+        &self,
+        _item_server_handles: &[u32],
+        _item_vqt: &[opc_da_bindings::tagOPCITEMVQT],
+        _transaction_id: u32,
+        _cancel_id: &mut u32,
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support async writes"))
+    }
+
+    fn active_items(&self) -> windows::core::Result<Vec<(u32, u32)>> {
+        Ok(self
+            .lock()
+            .items
+            .iter()
+            .filter(|(_, item)| item.active)
+            .map(|(server_handle, item)| (*server_handle, item.client_handle))
+            .collect())
+    }
+
+    fn data_callback(&self) -> Option<&DataCallbackConnectionPoint> {
+        self.connection_point
+            .get()
+            .map(|connection_point| -> &DataCallbackConnectionPoint { connection_point })
+    }
+
+    fn async_requests(&self) -> Option<&AsyncRequests> {
+        Some(&self.requests)
+    }
+
+    fn set_item_deadband(
+        &self,
+        _item_server_handles: &[u32],
+        _percent_deadband: &[f32],
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support item deadbands"))
+    }
+
+    fn get_item_deadband(
+        &self,
+        _item_server_handles: &[u32],
+        _percent_deadband: &mut [f32],
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support item deadbands"))
+    }
+
+    fn clear_item_deadband(
+        &self,
+        _item_server_handles: &[u32],
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support item deadbands"))
+    }
+
+    fn set_item_sampling_rate(
+        &self,
+        _count: u32,
+        _item_server_handles: &[u32],
+        _requested_sampling_rate: &[u32],
+        _revised_sampling_rate: &mut [u32],
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support item sampling"))
+    }
+
+    fn get_item_sampling_rate(
+        &self,
+        _item_server_handles: &[u32],
+        _sampling_rate: &mut [u32],
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support item sampling"))
+    }
+
+    fn clear_item_sampling_rate(
+        &self,
+        _item_server_handles: &[u32],
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support item sampling"))
+    }
+
+    fn set_item_buffer_enable(
+        &self,
+        _item_server_handles: &[u32],
+        _penable: &windows_core::BOOL,
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support item buffering"))
+    }
+
+    fn get_item_buffer_enable(
+        &self,
+        _item_server_handles: &[u32],
+        _enable: &mut [windows_core::BOOL],
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support item buffering"))
+    }
+
+    fn enum_connection_points(
+        &self,
+    ) -> windows::core::Result<windows::Win32::System::Com::IEnumConnectionPoints> {
+        Err(error::not_impl(
+            "MockGroup does not enumerate connection points",
+        ))
+    }
+
+    fn find_connection_point(
+        &self,
+        reference_interface_id: &windows::core::GUID,
+    ) -> windows::core::Result<windows::Win32::System::Com::IConnectionPoint> {
+        match self.connection_point.get() {
+            Some(connection_point)
+                if *reference_interface_id == opc_da_bindings::IOPCDataCallback::IID =>
+            {
+                Ok(connection_point.to_interface())
+            }
+            _ => Err(windows::Win32::System::Ole::CONNECT_E_NOCONNECTION.into()),
+        }
+    }
+
+    fn read3(
+        &self,
+        _connection: u32,
+        _source: opc_da_bindings::tagOPCDATASOURCE,
+        _item_server_handles: &[u32],
+        _transaction_id: &mut u32,
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support IOPCAsyncIO"))
+    }
+
+    fn write3(
+        &self,
+        _connection: u32,
+        _item_server_handles: &[u32],
+        _item_values: &[windows::Win32::System::Variant::VARIANT],
+        _transaction_id: &mut u32,
+        _errors: &mut [windows::core::HRESULT],
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support IOPCAsyncIO"))
+    }
+
+    fn refresh(
+        &self,
+        _connection: u32,
+        _source: opc_da_bindings::tagOPCDATASOURCE,
+    ) -> windows::core::Result<u32> {
+        Err(error::not_impl("MockGroup does not support IOPCAsyncIO"))
+    }
+
+    fn cancel(&self, _transaction_id: u32) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support IOPCAsyncIO"))
+    }
+
+    fn get_data(
+        &self,
+        _format_etc_in: &windows::Win32::System::Com::FORMATETC,
+    ) -> windows::core::Result<windows::Win32::System::Com::STGMEDIUM> {
+        Err(error::not_impl("MockGroup does not support IDataObject"))
+    }
+
+    fn get_data_here(
+        &self,
+        _format_etc_in: &windows::Win32::System::Com::FORMATETC,
+        _storage_medium: &mut windows::Win32::System::Com::STGMEDIUM,
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support IDataObject"))
+    }
+
+    fn query_get_data(
+        &self,
+        _format_etc_in: &windows::Win32::System::Com::FORMATETC,
+    ) -> windows::core::HRESULT {
+        windows::Win32::Foundation::E_NOTIMPL
+    }
+
+    fn get_canonical_format_etc(
+        &self,
+        _format_etc_in: &windows::Win32::System::Com::FORMATETC,
+        _format_etc_out: &mut windows::Win32::System::Com::FORMATETC,
+    ) -> windows::core::HRESULT {
+        windows::Win32::Foundation::E_NOTIMPL
+    }
+
+    fn set_data(
+        &self,
+        _format_etc_in: &windows::Win32::System::Com::FORMATETC,
+        _medium: &windows::Win32::System::Com::STGMEDIUM,
+        _release: windows_core::BOOL,
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support IDataObject"))
+    }
+
+    fn enum_format_etc(
+        &self,
+        _direction: u32,
+    ) -> windows::core::Result<windows::Win32::System::Com::IEnumFORMATETC> {
+        Err(error::not_impl("MockGroup does not support IDataObject"))
+    }
+
+    fn data_advise(
+        &self,
+        _format_etc_in: &windows::Win32::System::Com::FORMATETC,
+        _adv: u32,
+        _sink: windows::core::Ref<'_, windows::Win32::System::Com::IAdviseSink>,
+    ) -> windows::core::Result<u32> {
+        Err(error::not_impl("MockGroup does not support IDataObject"))
+    }
+
+    fn data_unadvise(&self, _connection: u32) -> windows::core::Result<()> {
+        Err(error::not_impl("MockGroup does not support IDataObject"))
+    }
+
+    fn enum_data_advise(
+        &self,
+    ) -> windows::core::Result<windows::Win32::System::Com::IEnumSTATDATA> {
+        Err(error::not_impl("MockGroup does not support IDataObject"))
+    }
+}
@@ -0,0 +1,32 @@
+//! In-memory [`ServerTrait`](crate::server::traits::ServerTrait) and
+//! [`GroupTrait`](crate::server::traits::GroupTrait) implementations for tests.
+//!
+//! `MockServer` keeps items in memory and derives its browse tree from the
+//! item IDs, split on a configurable separator. Read and write failures can be
+//! scripted per item. Wrap it in [`Server`](crate::server::com::server::Server)
+//! and call `into_object()` to expose it over COM in-process.
+//!
+//! `MockGroup` keeps an item table of its own and fires `OnDataChange` on
+//! demand through [`MockGroup::push_value`]. Groups added to a `MockServer`
+//! are `MockGroup`s, see [`MockServer::group`].
+
+mod group;
+mod server;
+
+pub use group::*;
+pub use server::*;
+
+/// `OPC_E_INVALIDHANDLE`: the server handle is not valid in the group.
+pub const OPC_E_INVALIDHANDLE: windows::core::HRESULT =
+    windows::core::HRESULT(0xC0040001u32 as i32);
+
+/// `OPC_E_DUPLICATENAME`: a group with the same name already exists.
+pub const OPC_E_DUPLICATENAME: windows::core::HRESULT =
+    windows::core::HRESULT(0xC004000Cu32 as i32);
+
+/// `OPC_E_UNKNOWNITEMID`: the item ID is not in the server address space.
+pub const OPC_E_UNKNOWNITEMID: windows::core::HRESULT =
+    windows::core::HRESULT(0xC0040007u32 as i32);
+
+/// OPC quality `GOOD`.
+const QUALITY_GOOD: u16 = 0xC0;
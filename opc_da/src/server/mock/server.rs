@@ -0,0 +1,531 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Mutex,
+};
+
+use windows::core::Interface as _;
+
+use crate::{
+    def::{EnumScope, ServerState, ServerStatus, Variant},
+    error,
+    server::{
+        com::{
+            enumeration::{StringEnumerator, UnknownEnumerator},
+            group::Group,
+        },
+        traits::{
+            AvailableProperty, BrowseDirection, BrowseElement, BrowseFilter, BrowseResult,
+            BrowseType, GroupInfo, GroupTrait as _, ItemOptionalVqt, ItemProperties,
+            ItemPropertyData, ItemWithMaxAge, NamespaceType, NewItem, ServerTrait, VqtWithError,
+        },
+    },
+};
+
+use super::{MockGroup, OPC_E_DUPLICATENAME, OPC_E_UNKNOWNITEMID, QUALITY_GOOD};
+
+/// A value stored by [`MockServer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockItem {
+    pub value: Variant,
+    pub quality: u16,
+    pub timestamp: std::time::SystemTime,
+}
+
+#[derive(Default)]
+struct MockState {
+    items: HashMap<String, MockItem>,
+    read_errors: HashMap<String, windows::core::HRESULT>,
+    write_errors: HashMap<String, windows::core::HRESULT>,
+    writes: Vec<(String, Variant)>,
+    locale_id: u32,
+    client_name: String,
+    next_group_handle: u32,
+    groups: BTreeMap<u32, windows::core::ComObject<Group<MockGroup>>>,
+}
+
+/// An in-memory OPC DA server with scriptable responses.
+pub struct MockServer {
+    state: Mutex<MockState>,
+    separator: char,
+    status: ServerStatus,
+}
+
+impl Default for MockServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockServer {
+    /// Creates an empty, running server using `.` as the browse separator.
+    pub fn new() -> Self {
+        let now = std::time::SystemTime::now();
+
+        Self {
+            state: Mutex::new(MockState::default()),
+            separator: '.',
+            status: ServerStatus {
+                start_time: now,
+                current_time: now,
+                last_update_time: now,
+                server_state: ServerState::Running,
+                group_count: 0,
+                band_width: ServerStatus::BANDWIDTH_UNKNOWN,
+                major_version: 1,
+                minor_version: 0,
+                build_number: 0,
+                vendor_info: "MockServer".to_string(),
+            },
+        }
+    }
+
+    /// Sets the separator used to derive the browse tree from item IDs.
+    pub fn with_separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets the status reported by `GetStatus`.
+    pub fn with_status(mut self, status: ServerStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Adds an item with good quality.
+    pub fn with_item<S: Into<String>>(self, item_id: S, value: Variant) -> Self {
+        self.set_item(item_id, value);
+        self
+    }
+
+    /// Inserts or replaces an item with good quality.
+    pub fn set_item<S: Into<String>>(&self, item_id: S, value: Variant) {
+        self.lock().items.insert(
+            item_id.into(),
+            MockItem {
+                value,
+                quality: QUALITY_GOOD,
+                timestamp: std::time::SystemTime::now(),
+            },
+        );
+    }
+
+    /// Returns a copy of the stored item.
+    pub fn item(&self, item_id: &str) -> Option<MockItem> {
+        self.lock().items.get(item_id).cloned()
+    }
+
+    /// Makes every read of `item_id` fail with `error`.
+    pub fn fail_read<S: Into<String>>(&self, item_id: S, error: windows::core::HRESULT) {
+        self.lock().read_errors.insert(item_id.into(), error);
+    }
+
+    /// Makes every write to `item_id` fail with `error`.
+    pub fn fail_write<S: Into<String>>(&self, item_id: S, error: windows::core::HRESULT) {
+        self.lock().write_errors.insert(item_id.into(), error);
+    }
+
+    /// Removes all scripted read and write failures.
+    pub fn clear_failures(&self) {
+        let mut state = self.lock();
+        state.read_errors.clear();
+        state.write_errors.clear();
+    }
+
+    /// Returns the successful writes, in call order.
+    pub fn writes(&self) -> Vec<(String, Variant)> {
+        self.lock().writes.clone()
+    }
+
+    /// Returns the group with the given name, to push values through it.
+    pub fn group(&self, name: &str) -> Option<windows::core::ComObject<Group<MockGroup>>> {
+        self.lock()
+            .groups
+            .values()
+            .find(|group| group.name() == name)
+            .cloned()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        // A panicking test must not poison the mock for the next assertion.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Returns the direct children of `parent` as `(name, item_id, is_item, has_children)`.
+    fn children(&self, parent: &str) -> Vec<(String, String, bool, bool)> {
+        let state = self.lock();
+        let prefix = if parent.is_empty() {
+            String::new()
+        } else {
+            format!("{parent}{}", self.separator)
+        };
+
+        let names: BTreeSet<&str> = state
+            .items
+            .keys()
+            .filter_map(|item_id| item_id.strip_prefix(&prefix))
+            .filter_map(|rest| rest.split(self.separator).next())
+            .filter(|name| !name.is_empty())
+            .collect();
+
+        names
+            .into_iter()
+            .map(|name| {
+                let item_id = format!("{prefix}{name}");
+                let branch_prefix = format!("{item_id}{}", self.separator);
+                let is_item = state.items.contains_key(&item_id);
+                let has_children = state.items.keys().any(|id| id.starts_with(&branch_prefix));
+                (name.to_string(), item_id, is_item, has_children)
+            })
+            .collect()
+    }
+}
+
+impl ServerTrait for MockServer {
+    fn set_locale_id(&self, locale_id: u32) -> windows::core::Result<()> {
+        self.lock().locale_id = locale_id;
+        Ok(())
+    }
+
+    fn get_locale_id(&self) -> windows::core::Result<u32> {
+        Ok(self.lock().locale_id)
+    }
+
+    fn query_available_locale_ids(&self) -> windows::core::Result<Vec<u32>> {
+        Ok(vec![0])
+    }
+
+    fn get_error_string(&self, error: i32) -> windows::core::Result<String> {
+        Ok(windows::core::HRESULT(error).message())
+    }
+
+    fn set_client_name(&self, name: String) -> windows::core::Result<()> {
+        self.lock().client_name = name;
+        Ok(())
+    }
+
+    fn enum_connection_points(
+        &self,
+    ) -> windows::core::Result<Vec<windows::Win32::System::Com::IConnectionPoint>> {
+        Ok(vec![])
+    }
+
+    fn find_connection_point(
+        &self,
+        _reference_interface_id: *const windows::core::GUID,
+    ) -> windows::core::Result<windows::Win32::System::Com::IConnectionPoint> {
+        Err(windows::core::Error::new(
+            windows::Win32::System::Ole::CONNECT_E_NOCONNECTION,
+            "MockServer has no connection points",
+        ))
+    }
+
+    fn query_available_properties(
+        &self,
+        item_id: String,
+    ) -> windows::core::Result<Vec<AvailableProperty>> {
+        let state = self.lock();
+        let item = state
+            .items
+            .get(&item_id)
+            .ok_or_else(|| windows::core::Error::new(OPC_E_UNKNOWNITEMID, "Unknown item ID"))?;
+
+        Ok(vec![AvailableProperty {
+            property_id: opc_da_bindings::OPC_PROPERTY_DATATYPE,
+            description: "Item Canonical DataType".to_string(),
+            data_type: item.value.get_data_type(),
+        }])
+    }
+
+    fn get_item_properties(
+        &self,
+        _item_id: String,
+        _property_ids: Vec<u32>,
+    ) -> windows::core::Result<Vec<ItemPropertyData>> {
+        Err(error::not_impl("MockServer does not serve item properties"))
+    }
+
+    fn lookup_item_ids(
+        &self,
+        _item_id: String,
+        _property_ids: Vec<u32>,
+    ) -> windows::core::Result<Vec<NewItem>> {
+        Err(error::not_impl("MockServer does not serve item properties"))
+    }
+
+    fn get_properties(
+        &self,
+        _item_ids: Vec<String>,
+        _return_property_values: bool,
+        _property_ids: Vec<u32>,
+    ) -> windows::core::Result<Vec<ItemProperties>> {
+        Err(error::not_impl("MockServer does not serve item properties"))
+    }
+
+    fn browse(
+        &self,
+        item_id: String,
+        _continuation_point: Option<String>,
+        _max_elements_returned: u32,
+        browse_filter: BrowseFilter,
+        _element_name_filter: String,
+        _vendor_filter: String,
+        _return_all_properties: bool,
+        _return_property_values: bool,
+        _property_ids: Vec<u32>,
+    ) -> windows::core::Result<BrowseResult> {
+        let elements = self
+            .children(&item_id)
+            .into_iter()
+            .filter(|(_, _, is_item, has_children)| match browse_filter {
+                BrowseFilter::All => true,
+                BrowseFilter::Branches => *has_children,
+                BrowseFilter::Items => *is_item,
+            })
+            .map(|(name, item_id, is_item, has_children)| {
+                let mut flag_value = 0;
+                if has_children {
+                    flag_value |= opc_da_bindings::OPC_BROWSE_HASCHILDREN;
+                }
+                if is_item {
+                    flag_value |= opc_da_bindings::OPC_BROWSE_ISITEM;
+                }
+
+                BrowseElement {
+                    name,
+                    item_id,
+                    flag_value,
+                    item_properties: ItemProperties {
+                        error_id: windows::core::HRESULT(0),
+                        item_properties: vec![],
+                    },
+                }
+            })
+            .collect();
+
+        Ok(BrowseResult {
+            more_elements: false,
+            continuation_point: None,
+            elements,
+        })
+    }
+
+    fn get_public_group_by_name(
+        &self,
+        _name: String,
+        _reference_interface_id: u128,
+    ) -> windows::core::Result<windows::core::IUnknown> {
+        Err(error::not_impl("MockServer does not support public groups"))
+    }
+
+    fn remove_public_group(&self, _server_group: u32, _force: bool) -> windows::core::Result<()> {
+        Err(error::not_impl("MockServer does not support public groups"))
+    }
+
+    fn query_organization(&self) -> windows::core::Result<NamespaceType> {
+        Ok(NamespaceType::Hierarchical)
+    }
+
+    fn change_browse_position(
+        &self,
+        _browse_direction: BrowseDirection,
+    ) -> windows::core::Result<()> {
+        Err(error::not_impl("MockServer only supports IOPCBrowse"))
+    }
+
+    fn browse_opc_item_ids(
+        &self,
+        _browse_filter_type: BrowseType,
+        _filter_criteria: String,
+        _variant_data_type_filter: u16,
+        _access_rights_filter: u32,
+    ) -> windows::core::Result<windows::Win32::System::Com::IEnumString> {
+        Err(error::not_impl("MockServer only supports IOPCBrowse"))
+    }
+
+    fn get_item_id(&self, item_data_id: String) -> windows::core::Result<String> {
+        Ok(item_data_id)
+    }
+
+    fn browse_access_paths(&self, _item_id: String) -> windows::core::Result<Vec<String>> {
+        Ok(vec![])
+    }
+
+    fn read(&self, items: Vec<ItemWithMaxAge>) -> windows::core::Result<Vec<VqtWithError>> {
+        let state = self.lock();
+
+        Ok(items
+            .into_iter()
+            .map(|item| {
+                let error = state.read_errors.get(&item.item_id).copied();
+                match (error, state.items.get(&item.item_id)) {
+                    (None, Some(stored)) => VqtWithError {
+                        value: stored.value.clone(),
+                        quality: stored.quality,
+                        timestamp: stored.timestamp,
+                        error: windows::core::HRESULT(0),
+                    },
+                    (error, _) => VqtWithError {
+                        value: Variant::Empty,
+                        quality: 0,
+                        timestamp: std::time::UNIX_EPOCH,
+                        error: error.unwrap_or(OPC_E_UNKNOWNITEMID),
+                    },
+                }
+            })
+            .collect())
+    }
+
+    fn write_vqt(
+        &self,
+        items: Vec<ItemOptionalVqt>,
+    ) -> windows::core::Result<Vec<windows::core::HRESULT>> {
+        let mut state = self.lock();
+
+        Ok(items
+            .into_iter()
+            .map(|item| {
+                if let Some(error) = state.write_errors.get(&item.item_id) {
+                    return *error;
+                }
+
+                let vqt = item.optional_vqt;
+                let Some(stored) = state.items.get_mut(&item.item_id) else {
+                    return OPC_E_UNKNOWNITEMID;
+                };
+
+                stored.value = vqt.value.clone();
+                stored.quality = vqt.quality.unwrap_or(QUALITY_GOOD);
+                stored.timestamp = vqt.timestamp.unwrap_or_else(std::time::SystemTime::now);
+                state.writes.push((item.item_id, vqt.value));
+
+                windows::core::HRESULT(0)
+            })
+            .collect())
+    }
+
+    fn add_group(
+        &self,
+        name: String,
+        active: bool,
+        requested_update_rate: u32,
+        client_group: u32,
+        time_bias: Option<i32>,
+        percent_deadband: Option<f32>,
+        locale_id: u32,
+        reference_interface_id: Option<u128>,
+    ) -> windows::core::Result<GroupInfo> {
+        let mut state = self.lock();
+        if state.groups.values().any(|group| group.name() == name) {
+            return Err(windows::core::Error::new(
+                OPC_E_DUPLICATENAME,
+                "A group with this name already exists",
+            ));
+        }
+
+        let server_group = state.next_group_handle + 1;
+        let name = if name.is_empty() {
+            format!("Group{server_group}")
+        } else {
+            name
+        };
+        let group = MockGroup::new(name, client_group, server_group).into_object();
+        let mut revised_update_rate = 0;
+        group.set_state(
+            &requested_update_rate,
+            &mut revised_update_rate,
+            &active.into(),
+            &time_bias.unwrap_or(0),
+            &percent_deadband.unwrap_or(0.0),
+            &locale_id,
+            &client_group,
+        )?;
+        let unknown = query_group(&group, reference_interface_id)?;
+
+        state.next_group_handle = server_group;
+        state.groups.insert(server_group, group);
+
+        Ok(GroupInfo {
+            server_group,
+            revised_update_rate,
+            unknown,
+        })
+    }
+
+    fn get_error_string_locale(&self, error: i32, _locale: u32) -> windows::core::Result<String> {
+        self.get_error_string(error)
+    }
+
+    fn get_group_by_name(
+        &self,
+        name: String,
+        reference_interface_id: Option<u128>,
+    ) -> windows::core::Result<windows::core::IUnknown> {
+        let group = self
+            .group(&name)
+            .ok_or_else(|| error::invalid_arg("Unknown group name"))?;
+        query_group(&group, reference_interface_id)
+    }
+
+    fn get_status(&self) -> windows::core::Result<ServerStatus> {
+        Ok(ServerStatus {
+            current_time: std::time::SystemTime::now(),
+            group_count: self.lock().groups.len() as u32,
+            ..self.status.clone()
+        })
+    }
+
+    fn remove_group(&self, server_group: u32, _force: bool) -> windows::core::Result<()> {
+        self.lock()
+            .groups
+            .remove(&server_group)
+            .map(|_| ())
+            .ok_or_else(|| error::invalid_arg("Unknown server group handle"))
+    }
+
+    fn create_group_enumerator(
+        &self,
+        scope: EnumScope,
+        reference_interface_id: Option<u128>,
+    ) -> windows::core::Result<windows::core::IUnknown> {
+        // Every mock group is private.
+        let groups: Vec<_> = match scope {
+            EnumScope::Public | EnumScope::PublicConnections => vec![],
+            _ => self.lock().groups.values().cloned().collect(),
+        };
+
+        let id = reference_interface_id.map_or(
+            windows::Win32::System::Com::IEnumUnknown::IID,
+            windows::core::GUID::from_u128,
+        );
+        if id == windows::Win32::System::Com::IEnumString::IID {
+            let names = groups.iter().map(|group| group.name()).collect();
+            Ok(windows::core::ComObject::new(StringEnumerator::new(names)).into_interface())
+        } else if id == windows::Win32::System::Com::IEnumUnknown::IID {
+            let unknowns = groups.iter().map(|group| group.to_interface()).collect();
+            Ok(windows::core::ComObject::new(UnknownEnumerator::new(unknowns)).into_interface())
+        } else {
+            Err(windows::Win32::Foundation::E_NOINTERFACE.into())
+        }
+    }
+}
+
+/// Returns the group as the interface requested by the client.
+fn query_group(
+    group: &windows::core::ComObject<Group<MockGroup>>,
+    reference_interface_id: Option<u128>,
+) -> windows::core::Result<windows::core::IUnknown> {
+    let unknown: windows::core::IUnknown = group.to_interface();
+    let Some(id) = reference_interface_id else {
+        return Ok(unknown);
+    };
+
+    let mut interface = core::ptr::null_mut();
+    unsafe {
+        unknown
+            .query(&windows::core::GUID::from_u128(id), &mut interface)
+            .ok()?;
+        Ok(windows::core::IUnknown::from_raw(interface))
+    }
+}
@@ -1,2 +1,4 @@
 pub mod com;
+#[cfg(feature = "test-util")]
+pub mod mock;
 pub mod traits;
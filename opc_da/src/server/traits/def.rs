@@ -1,18 +1,17 @@
+pub use crate::def::AvailableProperty;
+
+use opc_classic_utils::CalleeAllocatedWString;
+
 use crate::{
     def::ServerStatus,
     server::com::{
         base::Variant,
         utils::{PointerWriter, TryWriteArray, TryWriteTo},
+        variant::VariantCell,
     },
     utils::{ToNative as _, TryToLocal as _, TryToNative as _},
 };
 
-pub struct AvailableProperty {
-    pub property_id: u32,
-    pub description: String,
-    pub data_type: u16,
-}
-
 pub struct ItemPropertyData {
     pub property_id: u32,
     pub data: Variant,
@@ -158,19 +157,30 @@ impl TryFrom<ItemProperty> for opc_da_bindings::tagOPCITEMPROPERTY {
     type Error = windows::core::Error;
 
     fn try_from(value: ItemProperty) -> Result<Self, Self::Error> {
+        // Both strings are owned by guards until the struct is built, so a
+        // failed allocation frees the one allocated before it.
+        let item_id = write_wstring(&value.item_id)?;
+        let description = write_wstring(&value.description)?;
+        let value_cell = VariantCell::new(value.value);
         Ok(opc_da_bindings::tagOPCITEMPROPERTY {
             vtDataType: value.data_type,
             wReserved: 0,
             dwPropertyID: value.property_id,
-            szItemID: PointerWriter::try_write_to(&value.item_id)?,
-            szDescription: PointerWriter::try_write_to(&value.description)?,
-            vValue: value.value.into(),
+            szItemID: windows::core::PWSTR(item_id.into_raw()),
+            szDescription: windows::core::PWSTR(description.into_raw()),
+            vValue: value_cell.transfer(),
             hrErrorID: value.error_id,
             dwReserved: 0,
         })
     }
 }
 
+/// Allocates `value` with `CoTaskMemAlloc`, freed on drop unless released.
+fn write_wstring(value: &str) -> windows::core::Result<CalleeAllocatedWString> {
+    let pwstr: windows::core::PWSTR = PointerWriter::try_write_to(value)?;
+    Ok(unsafe { CalleeAllocatedWString::new(pwstr.0) })
+}
+
 impl From<BrowseFilter> for opc_da_bindings::tagOPCBROWSEFILTER {
     fn from(value: BrowseFilter) -> Self {
         match value {
@@ -197,6 +207,64 @@ impl TryFrom<opc_da_bindings::tagOPCBROWSEFILTER> for BrowseFilter {
     }
 }
 
+impl From<crate::def::BrowseFilter> for BrowseFilter {
+    fn from(value: crate::def::BrowseFilter) -> Self {
+        match value {
+            crate::def::BrowseFilter::All => BrowseFilter::All,
+            crate::def::BrowseFilter::Branches => BrowseFilter::Branches,
+            crate::def::BrowseFilter::Items => BrowseFilter::Items,
+        }
+    }
+}
+
+impl From<BrowseFilter> for crate::def::BrowseFilter {
+    fn from(value: BrowseFilter) -> Self {
+        match value {
+            BrowseFilter::All => crate::def::BrowseFilter::All,
+            BrowseFilter::Branches => crate::def::BrowseFilter::Branches,
+            BrowseFilter::Items => crate::def::BrowseFilter::Items,
+        }
+    }
+}
+
+impl From<crate::def::BrowseType> for BrowseType {
+    fn from(value: crate::def::BrowseType) -> Self {
+        match value {
+            crate::def::BrowseType::Branch => BrowseType::Branch,
+            crate::def::BrowseType::Leaf => BrowseType::Leaf,
+            crate::def::BrowseType::Flat => BrowseType::Flat,
+        }
+    }
+}
+
+impl From<BrowseType> for crate::def::BrowseType {
+    fn from(value: BrowseType) -> Self {
+        match value {
+            BrowseType::Branch => crate::def::BrowseType::Branch,
+            BrowseType::Leaf => crate::def::BrowseType::Leaf,
+            BrowseType::Flat => crate::def::BrowseType::Flat,
+        }
+    }
+}
+
+impl From<crate::def::NamespaceType> for NamespaceType {
+    fn from(value: crate::def::NamespaceType) -> Self {
+        match value {
+            crate::def::NamespaceType::Flat => NamespaceType::Flat,
+            crate::def::NamespaceType::Hierarchy => NamespaceType::Hierarchical,
+        }
+    }
+}
+
+impl From<NamespaceType> for crate::def::NamespaceType {
+    fn from(value: NamespaceType) -> Self {
+        match value {
+            NamespaceType::Flat => crate::def::NamespaceType::Flat,
+            NamespaceType::Hierarchical => crate::def::NamespaceType::Hierarchy,
+        }
+    }
+}
+
 impl TryFrom<BrowseElement> for opc_da_bindings::tagOPCBROWSEELEMENT {
     type Error = windows::core::Error;
 
@@ -289,6 +357,14 @@ impl From<BrowseType> for opc_da_bindings::tagOPCBROWSETYPE {
     }
 }
 
+impl ItemVqt {
+    /// Converts into a `tagOPCITEMVQT`, setting the `*Specified` flags from
+    /// the options. The caller owns the returned `VARIANT`.
+    pub fn into_native(self) -> windows::core::Result<opc_da_bindings::tagOPCITEMVQT> {
+        crate::def::item_vqt_to_native(self.value.into(), self.quality, self.timestamp)
+    }
+}
+
 impl TryFrom<opc_da_bindings::tagOPCITEMVQT> for ItemVqt {
     type Error = windows::core::Error;
 
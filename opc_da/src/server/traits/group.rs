@@ -1,3 +1,15 @@
+use windows::core::Interface as _;
+
+use crate::{
+    def::ItemAttributes,
+    error,
+    server::com::{
+        connection_point::DataCallbackConnectionPoint,
+        enumeration::ItemAttributesEnumerator,
+        request::{AsyncRequests, PendingRefresh},
+    },
+};
+
 pub trait GroupTrait {
     fn add_items(
         &self,
@@ -41,10 +53,28 @@ pub trait GroupTrait {
         errors: &mut [windows::core::HRESULT],
     ) -> windows::core::Result<()>;
 
+    /// Creates an `IEnumOPCItemAttributes` over [`item_attributes`](Self::item_attributes).
     fn create_enumerator(
         &self,
         reference_interface_id: &windows::core::GUID,
-    ) -> windows::core::Result<windows::core::IUnknown>;
+    ) -> windows::core::Result<windows::core::IUnknown> {
+        if *reference_interface_id != opc_da_bindings::IEnumOPCItemAttributes::IID {
+            return Err(windows::core::Error::new(
+                windows::Win32::Foundation::E_NOINTERFACE,
+                "Only IEnumOPCItemAttributes is supported",
+            ));
+        }
+
+        let enumerator: opc_da_bindings::IEnumOPCItemAttributes =
+            ItemAttributesEnumerator::new(self.item_attributes()?).into();
+
+        enumerator.cast()
+    }
+
+    /// Returns the attributes of all items in the group.
+    fn item_attributes(&self) -> windows::core::Result<Vec<ItemAttributes>> {
+        Err(error::not_impl("Item attributes listing not supported"))
+    }
 
     #[allow(clippy::too_many_arguments)]
     fn get_state(
@@ -144,7 +174,13 @@ pub trait GroupTrait {
         transaction_id: u32,
     ) -> windows::core::Result<u32>;
 
-    fn cancel2(&self, cancel_id: u32) -> windows::core::Result<()>;
+    fn cancel2(&self, cancel_id: u32) -> windows::core::Result<()> {
+        let requests = self
+            .async_requests()
+            .ok_or_else(|| error::not_impl("Async requests not supported"))?;
+
+        requests.cancel(cancel_id).map(|_| ())
+    }
 
     fn set_enable(&self, enable: windows_core::BOOL) -> windows::core::Result<()>;
 
@@ -168,7 +204,147 @@ pub trait GroupTrait {
         errors: &mut [windows::core::HRESULT],
     ) -> windows::core::Result<()>;
 
-    fn refresh_max_age(&self, max_age: u32, transaction_id: u32) -> windows::core::Result<u32>;
+    /// Queues an `OnDataChange` for all active items, honoring `max_age`.
+    ///
+    /// The default implementation validates the request and queues it on
+    /// [`GroupTrait::async_requests`]. The callback is fired once the
+    /// implementation calls [`GroupTrait::complete_refreshes`], which must
+    /// happen in the group's apartment, typically from its update loop. Until
+    /// then [`GroupTrait::cancel2`] can still drop the refresh.
+    ///
+    /// # Errors
+    /// Returns `E_FAIL` if the group is inactive or has no active items, and
+    /// `CONNECT_E_NOCONNECTION` if no data callback is advised.
+    fn refresh_max_age(&self, max_age: u32, transaction_id: u32) -> windows::core::Result<u32> {
+        let (connection_point, requests) = match (self.data_callback(), self.async_requests()) {
+            (Some(connection_point), Some(requests)) => (connection_point, requests),
+            _ => {
+                return Err(error::not_impl("RefreshMaxAge not supported"));
+            }
+        };
+
+        if !connection_point.has_sinks() {
+            return Err(windows::Win32::System::Ole::CONNECT_E_NOCONNECTION.into());
+        }
+
+        let (active, _) = group_state(self)?;
+        if !active || self.active_items()?.is_empty() {
+            return Err(error::fail("Group is inactive or has no active items"));
+        }
+
+        Ok(requests.queue_refresh(transaction_id, max_age))
+    }
+
+    /// Completes every queued refresh, see [`GroupTrait::complete_refresh`].
+    ///
+    /// Data callbacks are proxies of the apartment that advised them, so this
+    /// is called from the group's apartment rather than from a worker thread.
+    /// Failed refreshes are logged.
+    fn complete_refreshes(&self) {
+        let Some(requests) = self.async_requests() else {
+            return;
+        };
+
+        for refresh in requests.take_refreshes() {
+            if let Err(error) = self.complete_refresh(refresh) {
+                log::warn!("Refresh {} failed: {error}", refresh.transaction_id);
+            }
+        }
+    }
+
+    /// Reads the active items of a queued refresh and fires `OnDataChange`.
+    ///
+    /// The active items are read through [`GroupTrait::read_max_age`], so
+    /// values cached within `max_age` milliseconds are returned from cache and
+    /// older ones from the device. A refresh cancelled before the callback is
+    /// dropped.
+    fn complete_refresh(&self, refresh: PendingRefresh) -> windows::core::Result<()> {
+        let (connection_point, requests) = match (self.data_callback(), self.async_requests()) {
+            (Some(connection_point), Some(requests)) => (connection_point, requests),
+            _ => {
+                return Err(error::not_impl("RefreshMaxAge not supported"));
+            }
+        };
+
+        let read = group_state(self)
+            .and_then(|(_, group_client_handle)| Ok((group_client_handle, self.active_items()?)));
+        let (group_client_handle, items) = match read {
+            Ok(read) => read,
+            Err(error) => {
+                requests.finish(refresh.cancel_id);
+                return Err(error);
+            }
+        };
+
+        let (server_handles, client_handles): (Vec<_>, Vec<_>) = items.into_iter().unzip();
+        let count = server_handles.len();
+        let max_ages = vec![refresh.max_age; count];
+        let mut values: Vec<windows::Win32::System::Variant::VARIANT> =
+            (0..count).map(|_| Default::default()).collect();
+        let mut qualities = vec![0u16; count];
+        let mut timestamps = vec![windows::Win32::Foundation::FILETIME::default(); count];
+        let mut errors = vec![windows::core::HRESULT::default(); count];
+
+        let result = self
+            .read_max_age(
+                &server_handles,
+                &max_ages,
+                &mut values,
+                &mut qualities,
+                &mut timestamps,
+                &mut errors,
+            )
+            .and_then(|_| {
+                if !requests.finish(refresh.cancel_id) {
+                    return Ok(());
+                }
+
+                let master_quality = if qualities.iter().all(|quality| {
+                    quality & opc_da_bindings::OPC_QUALITY_MASK == opc_da_bindings::OPC_QUALITY_GOOD
+                }) {
+                    windows::Win32::Foundation::S_OK
+                } else {
+                    windows::Win32::Foundation::S_FALSE
+                };
+                let master_error = if errors.iter().all(|error| error.is_ok()) {
+                    windows::Win32::Foundation::S_OK
+                } else {
+                    windows::Win32::Foundation::S_FALSE
+                };
+
+                connection_point.broadcast_data_change(
+                    refresh.transaction_id,
+                    group_client_handle,
+                    master_quality,
+                    master_error,
+                    &client_handles,
+                    &values,
+                    &qualities,
+                    &timestamps,
+                    &errors,
+                )
+            });
+
+        // A failed read leaves the request pending, complete it either way.
+        requests.finish(refresh.cancel_id);
+
+        result
+    }
+
+    /// Returns the `(server handle, client handle)` pairs of all active items.
+    fn active_items(&self) -> windows::core::Result<Vec<(u32, u32)>> {
+        Err(error::not_impl("Active item listing not supported"))
+    }
+
+    /// Returns the connection point holding the advised `IOPCDataCallback` sinks.
+    fn data_callback(&self) -> Option<&DataCallbackConnectionPoint> {
+        None
+    }
+
+    /// Returns the tracker of pending asynchronous requests.
+    fn async_requests(&self) -> Option<&AsyncRequests> {
+        None
+    }
 
     fn set_item_deadband(
         &self,
@@ -316,3 +492,30 @@ pub trait GroupTrait {
     fn enum_data_advise(&self)
     -> windows::core::Result<windows::Win32::System::Com::IEnumSTATDATA>;
 }
+
+/// Returns whether the group is active and its client handle.
+fn group_state<T: GroupTrait + ?Sized>(group: &T) -> windows::core::Result<(bool, u32)> {
+    let mut update_rate = 0;
+    let mut active = windows_core::BOOL::default();
+    let mut name = windows::core::PWSTR::null();
+    let mut time_bias = 0;
+    let mut percent_deadband = 0.0;
+    let mut locale_id = 0;
+    let mut group_client_handle = 0;
+    let mut group_server_handle = 0;
+    group.get_state(
+        &mut update_rate,
+        &mut active,
+        &mut name,
+        &mut time_bias,
+        &mut percent_deadband,
+        &mut locale_id,
+        &mut group_client_handle,
+        &mut group_server_handle,
+    )?;
+    if !name.is_null() {
+        unsafe { windows::Win32::System::Com::CoTaskMemFree(Some(name.0 as _)) };
+    }
+
+    Ok((active.as_bool(), group_client_handle))
+}
@@ -219,6 +219,10 @@ pub trait ServerTrait {
         items: Vec<ItemOptionalVqt>,
     ) -> windows::core::Result<Vec<windows::core::HRESULT>>;
 
+    /// Creates a group and returns its handles.
+    ///
+    /// Implementations must reject a duplicate `name` with `OPC_E_DUPLICATENAME`
+    /// before allocating a server handle, so a failed call leaves no partial state.
     #[allow(clippy::too_many_arguments)]
     fn add_group(
         &self,
@@ -0,0 +1,35 @@
+/// Parses a GUID in registry format, with or without the surrounding braces.
+///
+/// # Errors
+/// Returns `E_INVALIDARG` if `s` is not a valid GUID.
+pub fn parse_guid(s: &str) -> windows::core::Result<windows::core::GUID> {
+    let trimmed = s.trim();
+    let inner = match trimmed.strip_prefix('{') {
+        Some(rest) => rest.strip_suffix('}'),
+        None => Some(trimmed),
+    };
+
+    inner
+        .and_then(|inner| windows::core::GUID::try_from(inner).ok())
+        .ok_or_else(|| crate::error::invalid_arg(format!("{s} is not a valid GUID")))
+}
+
+/// Resolves a server identifier given as either a CLSID or a ProgID.
+///
+/// `s` is parsed as a GUID first, see [`parse_guid`]. Anything else is looked
+/// up with `CLSIDFromProgID`, except strings starting with `{`, which are
+/// always meant as a GUID.
+///
+/// # Errors
+/// Returns `E_INVALIDARG` for a malformed GUID, and the `CLSIDFromProgID`
+/// error, usually `CO_E_CLASSSTRING`, if the ProgID is not registered.
+pub fn resolve_clsid(s: &str) -> windows::core::Result<windows::core::GUID> {
+    match parse_guid(s) {
+        Ok(guid) => Ok(guid),
+        Err(error) if s.trim_start().starts_with('{') => Err(error),
+        Err(_) => unsafe {
+            windows::Win32::System::Com::CLSIDFromProgID(&windows::core::HSTRING::from(s.trim()))
+        }
+        .map_err(|e| windows::core::Error::new(e.code(), format!("ProgID {s} is not registered"))),
+    }
+}
@@ -0,0 +1,18 @@
+/// Checks whether a COM object implements an interface.
+///
+/// Unlike `cast`, the reference added by `QueryInterface` is released before
+/// returning, so probing never holds on to the object.
+pub fn supports_interface(
+    unknown: &windows::core::IUnknown,
+    interface_id: &windows::core::GUID,
+) -> bool {
+    let mut interface = core::ptr::null_mut();
+    let result = unsafe { windows::core::Interface::query(unknown, interface_id, &mut interface) };
+
+    if !interface.is_null() {
+        // Takes ownership of the added reference and releases it on drop.
+        drop(unsafe { <windows::core::IUnknown as windows::core::Interface>::from_raw(interface) });
+    }
+
+    result.is_ok()
+}
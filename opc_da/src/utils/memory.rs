@@ -57,6 +57,26 @@ impl<T: Sized> RemoteArray<T> {
         }
     }
 
+    /// Creates a `RemoteArray` from a raw pointer and a length reported by the server.
+    ///
+    /// The length is clamped to `max` so that a buggy or hostile server cannot
+    /// make `as_slice` read far beyond its allocation. Clamping is logged.
+    ///
+    /// # Safety
+    /// The caller must ensure that the pointer is valid and points to a COM-allocated array.
+    #[inline(always)]
+    #[cfg(feature = "unstable_client")]
+    pub(crate) fn from_raw_checked(pointer: *const T, len: u32, max: u32) -> Self {
+        let len = if len > max {
+            log::warn!("clamping COM array length {len} to {max}");
+            max
+        } else {
+            len
+        };
+
+        Self::from_ptr(pointer, len)
+    }
+
     /// Creates an empty `RemoteArray`.
     #[inline(always)]
     pub fn empty() -> Self {
@@ -147,6 +167,44 @@ impl<T: Sized> RemoteArray<T> {
     }
 }
 
+impl RemoteArray<windows::Win32::System::Variant::VARIANT> {
+    /// Clears every `VARIANT` in place, freeing the `BSTR`s and interfaces they own.
+    ///
+    /// Dropping the array only frees the array itself, so callee-allocated
+    /// `VARIANT` arrays must be cleared first. Cleared elements are `VT_EMPTY`.
+    pub fn clear_variants(&mut self) {
+        for value in self.as_mut_slice() {
+            // Nothing useful can be done with a failure, the element is reset anyway.
+            let _ = unsafe { windows::Win32::System::Variant::VariantClear(value) };
+        }
+    }
+
+    /// Decodes the values, then clears them and frees the array.
+    pub fn into_variants(mut self) -> Vec<crate::def::Variant> {
+        let values = self
+            .as_slice()
+            .iter()
+            .map(crate::def::Variant::from)
+            .collect();
+        self.clear_variants();
+        values
+    }
+}
+
+impl RemoteArray<windows::core::HRESULT> {
+    /// Maps each per-item HRESULT to `Ok(())` or the matching error.
+    ///
+    /// Success codes such as `S_FALSE` or `OPC_S_CLAMP` map to `Ok(())`.
+    pub fn into_results(&self) -> Vec<windows::core::Result<()>> {
+        self.as_slice().iter().map(|error| error.ok()).collect()
+    }
+
+    /// Returns `true` if no per-item HRESULT is a failure.
+    pub fn all_succeeded(&self) -> bool {
+        self.as_slice().iter().all(|error| error.is_ok())
+    }
+}
+
 impl<T: Sized> Default for RemoteArray<T> {
     /// Creates an empty `RemoteArray` by default.
     #[inline(always)]
@@ -321,6 +379,9 @@ impl<T: Sized> Drop for RemotePointer<T> {
 /// This struct is useful for preparing data to be read by COM functions.
 pub struct LocalPointer<T: Sized> {
     inner: Option<Box<T>>,
+    /// Pointers into the strings of a `LocalPointer<Vec<Vec<u16>>>`, built on
+    /// the first call to `as_pcwstr_slice` and reset by mutable access.
+    pcwstrs: std::cell::OnceCell<Vec<windows::core::PCWSTR>>,
 }
 
 impl<T: Sized> LocalPointer<T> {
@@ -329,13 +390,17 @@ impl<T: Sized> LocalPointer<T> {
     pub fn new(value: Option<T>) -> Self {
         Self {
             inner: value.map(|v| Box::new(v)),
+            pcwstrs: std::cell::OnceCell::new(),
         }
     }
 
     /// Creates a `LocalPointer` from a boxed value.
     #[inline(always)]
     pub fn from_box(value: Box<T>) -> Self {
-        Self { inner: Some(value) }
+        Self {
+            inner: Some(value),
+            pcwstrs: std::cell::OnceCell::new(),
+        }
     }
 
     #[inline(always)]
@@ -358,6 +423,7 @@ impl<T: Sized> LocalPointer<T> {
     /// Returns a mutable pointer to the inner value.
     #[inline(always)]
     pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.pcwstrs.take();
         match &mut self.inner {
             Some(value) => value.as_mut() as *mut T,
             None => std::ptr::null_mut(),
@@ -431,6 +497,7 @@ impl<T> LocalPointer<Vec<T>> {
     /// Returns a mutable pointer to the inner array.
     #[inline(always)]
     pub fn as_mut_array_ptr(&mut self) -> *mut T {
+        self.pcwstrs.take();
         match &mut self.inner {
             Some(values) => values.as_mut_ptr(),
             None => std::ptr::null_mut(),
@@ -454,13 +521,39 @@ impl LocalPointer<Vec<Vec<u16>>> {
     /// Converts the inner vector of UTF-16 strings to a vector of `PCWSTR`.
     #[inline(always)]
     pub fn as_pcwstr_array(&self) -> Vec<windows::core::PCWSTR> {
-        match &self.inner {
-            Some(values) => values
+        self.pcwstr_iter().collect()
+    }
+
+    /// Iterates over the inner UTF-16 strings as `PCWSTR` without allocating.
+    ///
+    /// Yields a single null pointer if there is no inner value, matching
+    /// [`as_pcwstr_array`](Self::as_pcwstr_array). The pointers borrow the
+    /// string buffers and stay valid while this `LocalPointer` is alive and
+    /// unmodified, even if it is moved.
+    #[inline(always)]
+    pub fn pcwstr_iter(&self) -> impl Iterator<Item = windows::core::PCWSTR> + '_ {
+        let values = self.inner.as_deref().map(|values| {
+            values
                 .iter()
-                .map(|value| windows::core::PCWSTR::from_raw(value.as_ptr() as _))
-                .collect(),
-            None => vec![windows::core::PCWSTR::null()],
-        }
+                .map(|value| windows::core::PCWSTR::from_raw(value.as_ptr()))
+        });
+        let null = values
+            .is_none()
+            .then(windows::core::PCWSTR::null)
+            .into_iter();
+
+        values.into_iter().flatten().chain(null)
+    }
+
+    /// Returns the inner UTF-16 strings as a contiguous `PCWSTR` array.
+    ///
+    /// The array is built on the first call and reused afterwards, so passing
+    /// the same item IDs repeatedly allocates it once. It holds the same
+    /// pointers as [`pcwstr_iter`](Self::pcwstr_iter), valid while this
+    /// `LocalPointer` is alive and unmodified.
+    #[inline(always)]
+    pub fn as_pcwstr_slice(&self) -> &[windows::core::PCWSTR] {
+        self.pcwstrs.get_or_init(|| self.pcwstr_iter().collect())
     }
 }
 
@@ -483,3 +576,60 @@ impl LocalPointer<Vec<u16>> {
         }
     }
 }
+
+/// Caches the UTF-16 encoding of strings written repeatedly.
+///
+/// Converting a `Variant::String` encodes the string into a UTF-16 buffer
+/// on the heap, then copies it into a `BSTR`. For a value seen before, the
+/// cache reuses the encoded buffer and the `BSTR` is the only allocation,
+/// which pays off for batches where values repeat, such as enumeration
+/// states like `"RUNNING"` and `"STOPPED"`. `Group::write_items` hands the
+/// converted values to the server without copying them again.
+///
+/// `BSTR`s are not reference counted and every `VARIANT` frees its own, so
+/// each conversion still gets a fresh copy and never shares one.
+#[derive(Debug, Default)]
+pub struct StringWriteCache {
+    strings: std::collections::HashMap<String, Vec<u16>>,
+}
+
+impl StringWriteCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Converts `value` into a `VARIANT`, using the cache for strings.
+    pub fn to_variant(
+        &mut self,
+        value: &crate::def::Variant,
+    ) -> windows::Win32::System::Variant::VARIANT {
+        match value {
+            crate::def::Variant::String(value) => {
+                let wide = match self.strings.get(value.as_str()) {
+                    Some(wide) => wide,
+                    None => self
+                        .strings
+                        .entry(value.clone())
+                        .or_insert_with(|| value.encode_utf16().collect()),
+                };
+                windows::core::BSTR::from_wide(wide).into()
+            }
+            value => value.clone().into(),
+        }
+    }
+
+    /// Returns the number of distinct strings cached.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if no string is cached.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Forgets every cached string.
+    pub fn clear(&mut self) {
+        self.strings.clear();
+    }
+}
@@ -1,7 +1,11 @@
+mod clsid;
+mod interface;
 mod memory;
 mod native;
 mod try_iterator;
 
+pub use clsid::*;
+pub use interface::*;
 pub use memory::*;
 pub(crate) use native::*;
 pub use try_iterator::*;
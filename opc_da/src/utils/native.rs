@@ -68,7 +68,7 @@ impl TryFromNative<RemoteArray<windows::core::HRESULT>> for Vec<windows::core::R
     fn try_from_native(
         native: &RemoteArray<windows::core::HRESULT>,
     ) -> windows::core::Result<Self> {
-        Ok(native.as_slice().iter().map(|v| (*v).ok()).collect())
+        Ok(native.into_results())
     }
 }
 
@@ -107,6 +107,9 @@ impl<Native, T: TryFromNative<Native>>
     }
 }
 
+// `std::time::SystemTime` is the only time type of both the client and the
+// server definitions, so timestamps pass between them unchanged. These are the
+// only conversions from and to `FILETIME`.
 impl TryFromNative<windows::Win32::Foundation::FILETIME> for std::time::SystemTime {
     fn try_from_native(
         native: &windows::Win32::Foundation::FILETIME,